@@ -0,0 +1,61 @@
+//! Compares the allocations and time of rendering a big table with `hiccup_string!` and `hiccup_with_capacity!`.
+//! Run with `cargo bench --bench capacity`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use hiccup::{hiccup, hiccup_string, hiccup_with_capacity};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const ROWS: usize = 1_000;
+const RUNS: u32 = 100;
+
+fn bench(name: &str, render: impl Fn() -> String) {
+    let len = render().len();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        render();
+    }
+    let elapsed = start.elapsed() / RUNS;
+    let allocations = (ALLOCATIONS.load(Ordering::Relaxed) - allocations) / RUNS as usize;
+    println!("{:<22} {:>8} bytes {:>8} allocations {:>10.2?}/render", name, len, allocations, elapsed);
+}
+
+fn main() {
+    let rows: Vec<(usize, String)> = (0..ROWS).map(|i| (i, format!("row {}", i))).collect();
+    let len = hiccup_string!(table[for ((i, name) in &rows) [tr[td[(i)] td[(name)]]]]).len();
+
+    bench("hiccup_string!", || hiccup_string!(table[for ((i, name) in &rows) [tr[td[(i)] td[(name)]]]]));
+    bench("hiccup_with_capacity!", || {
+        hiccup_with_capacity!(len, table[for ((i, name) in &rows) [tr[td[(i)] td[(name)]]]])
+    });
+    bench("hiccup! into a buffer", || {
+        let mut out = String::with_capacity(len);
+        let _ = hiccup!(&mut out, table[for ((i, name) in &rows) [tr[td[(i)] td[(name)]]]]);
+        out
+    });
+}
@@ -0,0 +1,75 @@
+//! Compares `escape_html_into` with the char by char escaping it replaced, on a 100KB mostly plain text,
+//! and measures `hiccup!` writing the same text as a child and as an attribute value, which escape it while writing.
+//! Run with `cargo bench --bench escape`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use hiccup::escape::escape_html_into;
+use hiccup::hiccup;
+
+fn naive_escape_html_into(s: &str, out: &mut String) {
+    out.reserve(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+fn text() -> String {
+    let paragraph = "Hiccup renders HTML from Rust macros, like Clojure's hiccup does with vectors. ";
+    let mut text = String::new();
+    while text.len() < 100 * 1024 {
+        text.push_str(paragraph);
+        text.push_str("Rarely, a <b> & co. ");
+    }
+    text
+}
+
+fn escape(c: &mut Criterion) {
+    let text = text();
+    let mut group = c.benchmark_group("escape_html_into 100KB");
+    group.bench_function("naive", |b| {
+        b.iter(|| {
+            let mut out = String::new();
+            naive_escape_html_into(black_box(&text), &mut out);
+            out
+        })
+    });
+    group.bench_function("runs", |b| {
+        b.iter(|| {
+            let mut out = String::new();
+            escape_html_into(black_box(&text), &mut out);
+            out
+        })
+    });
+    group.finish();
+}
+
+fn render(c: &mut Criterion) {
+    let text = text();
+    let mut group = c.benchmark_group("hiccup! 100KB");
+    group.bench_function("text", |b| {
+        b.iter(|| {
+            let mut out = String::with_capacity(text.len() + 1024);
+            let _ = hiccup!(&mut out, p[(black_box(&text))]);
+            out
+        })
+    });
+    group.bench_function("attribute", |b| {
+        b.iter(|| {
+            let mut out = String::with_capacity(text.len() + 1024);
+            let _ = hiccup!(&mut out, div{title=>(black_box(&text))}[]);
+            out
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, escape, render);
+criterion_main!(benches);
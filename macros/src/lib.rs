@@ -0,0 +1,386 @@
+//! A `hiccup!` with the same syntax as `hiccup::hiccup!`, that parses the whole tree before expanding to it,
+//! so a syntax error is reported at the token that caused it instead of at the whole macro call.
+//!
+//! ```rust
+//! use hiccup_macros::hiccup;
+//!
+//! let mut html = String::new();
+//!
+//! let _ = hiccup!(&mut html, div.card{title=>"x"}[p["Hiccup"]]);
+//!
+//! assert_eq!(html, "<div title=\"x\" class=\"card\"><p>Hiccup</p></div>");
+//! ```
+//!
+//! A missing comma, like in `div{class=>"a" id=>"b"}`, is reported at `id` instead of at the whole call.
+//! The expansion is `hiccup::hiccup!`, so the crate using it needs `hiccup` as a dependency too.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Spacing, TokenTree};
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{ParseBuffer, ParseStream, Parser};
+use syn::{braced, bracketed, parenthesized, token, Block, Error, Expr, Ident, Lit, Pat, Result, Token};
+
+mod kw {
+    syn::custom_keyword!(doctype);
+    syn::custom_keyword!(raw);
+    syn::custom_keyword!(comment);
+    syn::custom_keyword!(cdata);
+    syn::custom_keyword!(cond_comment);
+    syn::custom_keyword!(entity);
+    syn::custom_keyword!(text);
+    syn::custom_keyword!(frag);
+    syn::custom_keyword!(meta_charset);
+    syn::custom_keyword!(csrf);
+}
+
+/// # `hiccup!`:
+/// Same syntax and output as `hiccup::hiccup!`, with the syntax errors reported at the offending token.
+#[proc_macro]
+pub fn hiccup(input: TokenStream) -> TokenStream {
+    let input = proc_macro2::TokenStream::from(input);
+    match document.parse2(input.clone()) {
+        Ok(()) => quote!(::hiccup::hiccup!(#input)).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+// The writer, then the tree with an optional leading `doctype`.
+fn document(input: ParseStream) -> Result<()> {
+    input.parse::<Expr>()?;
+    input.parse::<Token![,]>()?;
+    if input.peek(token::Brace) {
+        braces(input, "")?.call(Block::parse_within)?;
+    }
+    if input.peek(kw::doctype) {
+        input.parse::<kw::doctype>()?;
+    }
+    tree(input)
+}
+
+fn tree(input: ParseStream) -> Result<()> {
+    while !input.is_empty() {
+        node(input)?;
+    }
+    Ok(())
+}
+
+fn node(input: ParseStream) -> Result<()> {
+    if input.peek(token::Paren) {
+        return expr(&parens(input, "hiccup: expected `(expression)`")?);
+    }
+    if input.peek(Lit) {
+        return input.parse::<Lit>().map(|_| ());
+    }
+    if input.peek(Token![?]) {
+        input.parse::<Token![?]>()?;
+        if input.is_empty() {
+            return Err(input.error("hiccup: expected an `Option` after `?`"));
+        }
+        return input.parse::<TokenTree>().map(|_| ());
+    }
+    if input.peek(Token![-]) && input.peek2(Lit) {
+        input.parse::<Token![-]>()?;
+        return input.parse::<Lit>().map(|_| ());
+    }
+    if input.peek(Token![if]) {
+        input.parse::<Token![if]>()?;
+        expr(&parens(input, "hiccup: expected a parenthesized condition, like `if (cond) [...]`")?)?;
+        tree(&brackets(input, "hiccup: expected `[...]` after the condition of `if`")?)?;
+        if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            tree(&brackets(input, "hiccup: expected `[...]` after `else`")?)?;
+        }
+        return Ok(());
+    }
+    if input.peek(Token![for]) {
+        input.parse::<Token![for]>()?;
+        let header = parens(input, "hiccup: expected `(item in items)` after `for`")?;
+        header.call(Pat::parse_multi_with_leading_vert)?;
+        if !header.peek(Token![in]) {
+            return Err(header.error("hiccup: expected `in` after the pattern of `for`"));
+        }
+        header.parse::<Token![in]>()?;
+        expr(&header)?;
+        return tree(&brackets(input, "hiccup: expected `[...]` after `for (item in items)`")?);
+    }
+    if input.peek(Token![let]) {
+        input.parse::<Token![let]>()?;
+        input.call(Pat::parse_multi_with_leading_vert)?;
+        input.parse::<Token![=]>()?;
+        expr(&parens(input, "hiccup: expected a parenthesized value, like `let name = (value);`")?)?;
+        return input.parse::<Token![;]>().map(|_| ());
+    }
+    if input.peek(Token![match]) {
+        input.parse::<Token![match]>()?;
+        expr(&parens(input, "hiccup: expected a parenthesized value, like `match (value) { ... }`")?)?;
+        return match_arms(&braces(input, "hiccup: expected `{ Pattern => [...], ... }` after the value of `match`")?);
+    }
+    if input.peek(kw::doctype) {
+        return Err(input.error("hiccup: `doctype` is only valid as the first element"));
+    }
+    if (input.peek(kw::raw) || input.peek(kw::comment) || input.peek(kw::cdata)) && input.peek2(token::Bracket) {
+        input.call(Ident::parse_any)?;
+        // Written with `write!`, any value is passed as is.
+        return brackets(input, "")?.parse::<proc_macro2::TokenStream>().map(|_| ());
+    }
+    if input.peek(kw::frag) && input.peek2(token::Bracket) {
+        input.parse::<kw::frag>()?;
+        return tree(&brackets(input, "")?);
+    }
+    if input.peek(kw::cond_comment) && input.peek2(token::Bracket) {
+        input.parse::<kw::cond_comment>()?;
+        let cond = brackets(input, "")?;
+        if !cond.peek(Lit) {
+            return Err(cond.error("hiccup: expected a condition string, like `cond_comment[\"lt IE 9\"][...]`"));
+        }
+        cond.parse::<Lit>()?;
+        end(&cond, "hiccup: expected only the condition string in `cond_comment[...]`")?;
+        return tree(&brackets(input, "hiccup: expected `[...]` after the condition of `cond_comment`")?);
+    }
+    if input.peek(kw::entity) && input.peek2(token::Paren) {
+        input.parse::<kw::entity>()?;
+        let name = parens(input, "")?;
+        name.call(Ident::parse_any)?;
+        return end(&name, "hiccup: expected only the entity name, like `entity(nbsp)`");
+    }
+    if input.peek(kw::text) && input.peek2(Token![!]) {
+        input.parse::<kw::text>()?;
+        input.parse::<Token![!]>()?;
+        let args = parens(input, "hiccup: expected `(\"format\", args...)` after `text!`")?;
+        return args.parse::<proc_macro2::TokenStream>().map(|_| ());
+    }
+    if input.peek(kw::meta_charset) {
+        input.parse::<kw::meta_charset>()?;
+        if input.peek(token::Bracket) {
+            expr(&brackets(input, "")?)?;
+        }
+        return Ok(());
+    }
+    if input.peek(kw::csrf) && input.peek2(token::Paren) {
+        input.parse::<kw::csrf>()?;
+        return expr(&parens(input, "")?);
+    }
+    if input.peek(Ident::peek_any) {
+        return tag(input);
+    }
+    Err(unexpected(input, "a tag, a text or a parenthesized expression"))
+}
+
+fn match_arms(input: ParseStream) -> Result<()> {
+    while !input.is_empty() {
+        input.call(Pat::parse_multi_with_leading_vert)?;
+        if input.peek(Token![if]) {
+            input.parse::<Token![if]>()?;
+            input.parse::<Expr>()?;
+        }
+        input.parse::<Token![=>]>()?;
+        tree(&brackets(input, "hiccup: expected `[...]` after `=>` in a `match` arm")?)?;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+    }
+    Ok(())
+}
+
+// The name with its hyphens and shorthands, the attributes, the children and the attribute blocks after them.
+fn tag(input: ParseStream) -> Result<()> {
+    let mut name = input.call(Ident::parse_any)?.unraw().to_string();
+    while input.peek(Token![-]) && input.peek2(Ident::peek_any) {
+        input.parse::<Token![-]>()?;
+        name = format!("{}-{}", name, input.call(Ident::parse_any)?);
+    }
+    let mut id = false;
+    loop {
+        if input.peek(Token![.]) {
+            input.parse::<Token![.]>()?;
+            if !input.peek(Ident::peek_any) {
+                return Err(input.error("hiccup: expected a class name after `.`"));
+            }
+            input.call(Ident::parse_any)?;
+        } else if input.peek(Token![#]) {
+            let hash = input.parse::<Token![#]>()?;
+            if id {
+                return Err(Error::new(hash.span, format!("hiccup: `{}` can have only one `#id`", name)));
+            }
+            if !input.peek(Ident::peek_any) {
+                return Err(input.error("hiccup: expected an id after `#`"));
+            }
+            input.call(Ident::parse_any)?;
+            id = true;
+        } else {
+            break;
+        }
+    }
+    // The `#id` shorthand and an `id` attribute would write the attribute twice.
+    let id = if id { Some(name.as_str()) } else { None };
+    if input.peek(token::Brace) {
+        attrs(&braces(input, "")?, id)?;
+    }
+    if input.peek(Token![/]) {
+        return input.parse::<Token![/]>().map(|_| ());
+    }
+    if input.peek(token::Bracket) {
+        tree(&brackets(input, "")?)?;
+        while input.peek(token::Brace) {
+            attrs(&braces(input, "")?, id)?;
+        }
+    }
+    Ok(())
+}
+
+fn attrs(input: ParseStream, id_shorthand: Option<&str>) -> Result<()> {
+    loop {
+        while input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+        if input.is_empty() {
+            return Ok(());
+        }
+        if input.peek(Token![..]) {
+            input.parse::<Token![..]>()?;
+            input.parse::<Expr>()?;
+            if !input.is_empty() && !input.peek(Token![,]) {
+                return Err(input.error("hiccup: expected `,` or the end of the attributes after `..attrs`"));
+            }
+            continue;
+        }
+        let span = input.span();
+        let key = attr_key(input)?;
+        if let (Some(name), "id") = (id_shorthand, key.as_str()) {
+            return Err(Error::new(span, format!("hiccup: `{}` can have only one `#id`", name)));
+        }
+        let mut valued = false;
+        if input.peek(Token![=>]) {
+            let arrow = input.parse::<Token![=>]>()?;
+            if input.is_empty() || input.peek(Token![,]) {
+                return Err(Error::new(arrow.spans[1], format!("hiccup: expected a value after `{}=>`", key)));
+            }
+            attr_value(input)?;
+            valued = true;
+        } else if input.peek(Token![if]) {
+            input.parse::<Token![if]>()?;
+            input.parse::<Expr>()?;
+        }
+        if !input.is_empty() && !input.peek(Token![,]) {
+            let message = if valued {
+                format!("hiccup: missing `,` after the value of attribute `{}`", key)
+            } else {
+                format!("hiccup: expected `=> value`, `,` or the end of the attributes after attribute key `{}`", key)
+            };
+            return Err(input.error(message));
+        }
+    }
+}
+
+// Returns the key as written in the markup, for the error messages.
+fn attr_key(input: ParseStream) -> Result<String> {
+    if input.peek(Ident::peek_any) {
+        let mut key = input.call(Ident::parse_any)?.unraw().to_string();
+        if input.peek(Token![:]) && input.peek2(Ident::peek_any) {
+            input.parse::<Token![:]>()?;
+            key = format!("{}:{}", key, input.call(Ident::parse_any)?.unraw());
+        }
+        return Ok(key);
+    }
+    match input.parse::<Lit>() {
+        Ok(Lit::Str(key)) => Ok(key.value()),
+        Ok(key) => Ok(quote!(#key).to_string()),
+        Err(_) => Err(unexpected(input, "an attribute key, like `class` or `\"aria-label\"`")),
+    }
+}
+
+fn attr_value(input: ParseStream) -> Result<()> {
+    if input.peek(Token![?]) || input.peek(Token![@]) {
+        input.parse::<TokenTree>()?;
+        return input.parse::<TokenTree>().map(|_| ());
+    }
+    if input.peek(token::Bracket) {
+        let list = brackets(input, "")?;
+        while !list.is_empty() {
+            list.parse::<TokenTree>()?;
+            if list.peek(Token![if]) {
+                list.parse::<Token![if]>()?;
+                list.parse::<Expr>()?;
+            }
+            if !list.is_empty() {
+                list.parse::<Token![,]>()?;
+            }
+        }
+        return Ok(());
+    }
+    if input.peek(token::Brace) {
+        let styles = braces(input, "")?;
+        while !styles.is_empty() {
+            attr_key(&styles)?;
+            styles.parse::<Token![=>]>()?;
+            styles.parse::<Expr>()?;
+            if !styles.is_empty() {
+                styles.parse::<Token![,]>()?;
+            }
+        }
+        return Ok(());
+    }
+    input.parse::<Expr>().map(|_| ())
+}
+
+// A single expression filling the group.
+fn expr(input: ParseStream) -> Result<()> {
+    input.parse::<Expr>()?;
+    end(input, "hiccup: expected a single expression")
+}
+
+fn end(input: ParseStream, message: &str) -> Result<()> {
+    if input.is_empty() {
+        Ok(())
+    } else {
+        Err(input.error(message))
+    }
+}
+
+// The content of the next `(...)`, or an error with `message`. An empty `message` is only for groups already peeked.
+fn parens<'a>(input: &ParseBuffer<'a>, message: &str) -> Result<ParseBuffer<'a>> {
+    if !input.peek(token::Paren) {
+        return Err(input.error(message));
+    }
+    let content;
+    parenthesized!(content in input);
+    Ok(content)
+}
+
+fn brackets<'a>(input: &ParseBuffer<'a>, message: &str) -> Result<ParseBuffer<'a>> {
+    if !input.peek(token::Bracket) {
+        return Err(input.error(message));
+    }
+    let content;
+    bracketed!(content in input);
+    Ok(content)
+}
+
+fn braces<'a>(input: &ParseBuffer<'a>, message: &str) -> Result<ParseBuffer<'a>> {
+    if !input.peek(token::Brace) {
+        return Err(input.error(message));
+    }
+    let content;
+    braced!(content in input);
+    Ok(content)
+}
+
+// An error at the next token, with the punctuation joined like `=>` is written.
+fn unexpected(input: ParseStream, expected: &str) -> Error {
+    let span = input.span();
+    let mut token = String::new();
+    let _ = input.step(|cursor| {
+        let mut rest = *cursor;
+        while let Some((tt, next)) = rest.token_tree() {
+            token.push_str(&tt.to_string());
+            rest = next;
+            match tt {
+                TokenTree::Punct(punct) if punct.spacing() == Spacing::Joint => {}
+                _ => break,
+            }
+        }
+        Ok(((), rest))
+    });
+    Error::new(span, format!("hiccup: unexpected `{}`, expected {}", token, expected))
+}
@@ -0,0 +1,48 @@
+use hiccup_macros::hiccup;
+
+#[test]
+fn same_output_as_hiccup() {
+    let mut out = String::new();
+    let mut expected = String::new();
+    let items = ["a", "b"];
+    let url: Option<&str> = None;
+    let busy = true;
+
+    let _ = hiccup!(&mut out, { let lang = "en"; }
+        doctype html{lang=>(lang)}[
+            head[meta_charset title["Tom & Jerry"]]
+            body#main.page{class=>["dark" if busy, "wide"], data_x=>(1 + 2)}[
+                my-widget{"aria-label"=>"close", hidden}/
+                a{href=>?url, class=>@(["x", ""]), ..vec![("rel", "next")]}["home"]{title=>"t"}
+                p{}["a"]{id=>"b"}{hidden}
+                p{style=>{color=>"red", font_weight=>"bold"}}["a " ?url b[text!("{}", 1 < 2)] entity(nbsp) (-1)]
+                ul[for (item in &items) [li[(item)]]]
+                let total = (items.len());
+                if (total > 1) [frag[i["many"]]] else [i["one"]]
+                match (total) { 0 => [], n if n > 1 => [span[(n)]], _ => [] }
+                button{disabled if busy, type=>"submit"} csrf("a&b")
+                svg[use{xlink:href=>"#icon"}]
+                raw["<hr>"] comment["c"] cond_comment["lt IE 9"][script{src=>"shim.js"}[]] -1 true
+            ]
+        ]);
+    let _ = hiccup::hiccup!(&mut expected, { let lang = "en"; }
+        doctype html{lang=>(lang)}[
+            head[meta_charset title["Tom & Jerry"]]
+            body#main.page{class=>["dark" if busy, "wide"], data_x=>(1 + 2)}[
+                my-widget{"aria-label"=>"close", hidden}/
+                a{href=>?url, class=>@(["x", ""]), ..vec![("rel", "next")]}["home"]{title=>"t"}
+                p{}["a"]{id=>"b"}{hidden}
+                p{style=>{color=>"red", font_weight=>"bold"}}["a " ?url b[text!("{}", 1 < 2)] entity(nbsp) (-1)]
+                ul[for (item in &items) [li[(item)]]]
+                let total = (items.len());
+                if (total > 1) [frag[i["many"]]] else [i["one"]]
+                match (total) { 0 => [], n if n > 1 => [span[(n)]], _ => [] }
+                button{disabled if busy, type=>"submit"} csrf("a&b")
+                svg[use{xlink:href=>"#icon"}]
+                raw["<hr>"] comment["c"] cond_comment["lt IE 9"][script{src=>"shim.js"}[]] -1 true
+            ]
+        ]);
+
+    assert_eq!(out, expected);
+    assert!(out.starts_with("<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\"/>"));
+}
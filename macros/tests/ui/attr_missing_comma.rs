@@ -0,0 +1,7 @@
+use hiccup_macros::hiccup;
+
+fn main() {
+    let mut out = String::new();
+
+    let _ = hiccup!(&mut out, div{class=>"a" id=>"b"}["x"]);
+}
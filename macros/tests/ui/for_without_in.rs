@@ -0,0 +1,7 @@
+use hiccup_macros::hiccup;
+
+fn main() {
+    let mut out = String::new();
+
+    let _ = hiccup!(&mut out, ul[for (item of items) [li[(item)]]]);
+}
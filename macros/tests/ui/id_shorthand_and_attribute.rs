@@ -0,0 +1,8 @@
+use hiccup_macros::hiccup;
+
+fn main() {
+    let mut out = String::new();
+
+    let _ = hiccup!(&mut out, div#a{class=>"x", id=>"b"}["x"]);
+    let _ = hiccup!(&mut out, div#a["x"]{"id"=>"b"});
+}
@@ -0,0 +1,104 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::is_void_element;
+
+/// Checks that every start tag of `html` is closed, in order, by `hiccup_checked!`.
+/// Void and self-closed elements don't need an end tag, and comments, CDATA, the doctype
+/// and the text of `script` and `style` are skipped.
+#[doc(hidden)]
+pub fn check_balanced(html: &str) -> Result<(), String> {
+    let mut open: Vec<&str> = Vec::new();
+    let mut rest = html;
+    while let Some(i) = rest.find('<') {
+        rest = &rest[i..];
+        if let Some(skipped) = skip(rest, "<!--", "-->")
+            .or_else(|| skip(rest, "<![CDATA[", "]]>"))
+            .or_else(|| skip(rest, "<!", ">"))
+            .or_else(|| skip(rest, "<?", "?>"))
+        {
+            rest = skipped?;
+        } else if let Some(end) = rest.strip_prefix("</") {
+            let name = tag_name(end);
+            match open.pop() {
+                Some(start) if start.eq_ignore_ascii_case(name) => {}
+                Some(start) => return Err(format!("`</{}>` closes `<{}>`", name, start)),
+                None => return Err(format!("`</{}>` has no start tag", name)),
+            }
+            rest = after_tag(end).ok_or_else(|| format!("`</{}` isn't closed by `>`", name))?;
+        } else {
+            let name = tag_name(&rest[1..]);
+            if name.is_empty() {
+                rest = &rest[1..];
+                continue;
+            }
+            let after = after_tag(&rest[1..]).ok_or_else(|| format!("`<{}` isn't closed by `>`", name))?;
+            let self_closed = rest[..rest.len() - after.len()].ends_with("/>");
+            rest = after;
+            if self_closed || is_void_element(&name.to_ascii_lowercase()) {
+                continue;
+            }
+            if ["script", "style"].iter().any(|raw| raw.eq_ignore_ascii_case(name)) {
+                let end = rest.to_ascii_lowercase().find(&format!("</{}", name.to_ascii_lowercase()));
+                rest = &rest[end.ok_or_else(|| format!("`<{}>` isn't closed", name))?..];
+            }
+            open.push(name);
+        }
+    }
+    match open.pop() {
+        Some(start) => Err(format!("`<{}>` isn't closed", start)),
+        None => Ok(()),
+    }
+}
+
+// The rest after the `end` of a construct starting with `start`, or an error when it's never ended.
+fn skip<'a>(s: &'a str, start: &str, end: &str) -> Option<Result<&'a str, String>> {
+    let body = s.strip_prefix(start)?;
+    Some(body.find(end).map(|i| &body[i + end.len()..]).ok_or_else(|| format!("`{}` isn't closed by `{}`", start, end)))
+}
+
+fn tag_name(s: &str) -> &str {
+    let len = s.find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))).unwrap_or(s.len());
+    if s.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        &s[..len]
+    } else {
+        ""
+    }
+}
+
+// The rest after the `>` of a tag, skipping the `>` inside quoted attribute values.
+fn after_tag(s: &str) -> Option<&str> {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '>') => return Some(&s[i + 1..]),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_balanced;
+
+    #[test]
+    fn balanced_markup() {
+        assert_eq!(check_balanced("<div id=\"a>b\"><p>x<br/><img src=\"a\"><hr></p></div>"), Ok(()));
+        assert_eq!(check_balanced("<!DOCTYPE html><html><!-- <div> --><![CDATA[<p>]]></html>"), Ok(()));
+        assert_eq!(check_balanced("<script>if (a < b) { x = \"<div>\" }</script><p>1 &lt; 2</p>"), Ok(()));
+        assert_eq!(check_balanced("a < b"), Ok(()));
+    }
+
+    #[test]
+    fn unbalanced_markup() {
+        assert_eq!(check_balanced("<div><p>x</div>"), Err("`</div>` closes `<p>`".into()));
+        assert_eq!(check_balanced("<div>"), Err("`<div>` isn't closed".into()));
+        assert_eq!(check_balanced("</p>"), Err("`</p>` has no start tag".into()));
+        assert_eq!(check_balanced("<!-- x"), Err("`<!--` isn't closed by `-->`".into()));
+        assert_eq!(check_balanced("<a href=\"x\""), Err("`<a` isn't closed by `>`".into()));
+    }
+}
@@ -0,0 +1,499 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::vec;
+use core::fmt::{self, Write};
+use core::mem;
+
+use crate::render::{is_valid_name, Context, PrettyConfig, Render, Renderer};
+
+/// A tree built at runtime, for when the tags aren't known at compile time.
+/// It renders exactly like the same tree written with `hiccup!`.
+/// A tag name or an attribute key that isn't a name, like one with a space, a `>` or a `=`, fails the rendering with `fmt::Error`.
+///
+/// ```rust
+/// use hiccup::Element;
+///
+/// let mut html = String::new();
+/// let card = Element::new("div").attr("class", "card").child(Element::text("hi"));
+///
+/// card.render_to(&mut html).unwrap();
+///
+/// assert_eq!(html, "<div class=\"card\">hi</div>");
+/// assert_eq!(card.to_string(), html);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    node: Node,
+    // Only used when this element is the one rendered, not when it's a child.
+    trailing_newline: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Tag {
+        name: String,
+        // In insertion order, `None` values are boolean attributes.
+        attrs: Vec<(String, Option<String>)>,
+        children: Vec<Element>,
+    },
+    Text(String),
+}
+
+impl Element {
+    /// An element without attributes or children, like `hiccup!(w, div)`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Element {
+            node: Node::Tag { name: name.into(), attrs: Vec::new(), children: Vec::new() },
+            trailing_newline: false,
+        }
+    }
+
+    /// A text node, escaped when rendered.
+    pub fn text(text: impl Into<String>) -> Self {
+        Element { node: Node::Text(text.into()), trailing_newline: false }
+    }
+
+    /// Adds a `key="value"` attribute, after the ones already added. Text nodes are left unchanged.
+    pub fn attr(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        if let Node::Tag { attrs, .. } = &mut self.node {
+            attrs.push((key.into(), Some(value.into())));
+        }
+        self
+    }
+
+    /// Adds a boolean attribute, written without a value. Text nodes are left unchanged.
+    pub fn bool_attr(mut self, key: impl Into<String>) -> Self {
+        if let Node::Tag { attrs, .. } = &mut self.node {
+            attrs.push((key.into(), None));
+        }
+        self
+    }
+
+    /// Appends a child. Text nodes are left unchanged.
+    pub fn child(mut self, child: Element) -> Self {
+        if let Node::Tag { children, .. } = &mut self.node {
+            children.push(child);
+        }
+        self
+    }
+
+    /// The name of the tag, `None` for a text node.
+    pub fn tag_name(&self) -> Option<&str> {
+        match &self.node {
+            Node::Tag { name, .. } => Some(name),
+            Node::Text(_) => None,
+        }
+    }
+
+    /// The children, in order, empty for a text node.
+    pub fn children(&self) -> &[Element] {
+        match &self.node {
+            Node::Tag { children, .. } => children,
+            Node::Text(_) => &[],
+        }
+    }
+
+    /// Every element of the tree named `name`, this one included, in document order.
+    ///
+    /// ```rust
+    /// use hiccup::Element;
+    ///
+    /// let list = Element::new("ul").child(Element::new("li")).child(Element::new("li"));
+    ///
+    /// assert_eq!(list.find_all("li").len(), 2);
+    /// ```
+    pub fn find_all(&self, name: &str) -> Vec<&Element> {
+        let mut found = Vec::new();
+        self.find_all_into(name, &mut found);
+        found
+    }
+
+    fn find_all_into<'a>(&'a self, name: &str, found: &mut Vec<&'a Element>) {
+        if self.tag_name() == Some(name) {
+            found.push(self);
+        }
+        for child in self.children() {
+            child.find_all_into(name, found);
+        }
+    }
+
+    /// Whether a `\n` is written after the tree when it's rendered, for files checked by tools that expect a final newline.
+    /// The children are rendered without it.
+    pub fn with_trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
+    /// Replaces every text node of the tree, before it's escaped, with the result of `f`,
+    /// like to highlight words or to detect links.
+    pub fn transform_text(mut self, f: impl Fn(&str) -> String) -> Self {
+        self.transform_text_with(&f);
+        self
+    }
+
+    fn transform_text_with(&mut self, f: &dyn Fn(&str) -> String) {
+        match &mut self.node {
+            Node::Text(text) => *text = f(text),
+            Node::Tag { children, .. } => {
+                for child in children {
+                    child.transform_text_with(f);
+                }
+            }
+        }
+    }
+
+    /// Renders the tree into any writer, like a `String` or a `&mut dyn Write`.
+    pub fn render_to<W: Write + ?Sized>(&self, w: &mut W) -> fmt::Result {
+        self.render_with(&mut Renderer::new(w))?;
+        if self.trailing_newline {
+            w.write_str("\n")?;
+        }
+        Ok(())
+    }
+
+    /// Renders the tree like `hiccup_pretty!`, with the indentation and the line break of `config`.
+    pub fn render_pretty<W: Write + ?Sized>(&self, w: &mut W, config: &PrettyConfig) -> fmt::Result {
+        self.render_with(&mut Renderer::pretty_with(w, config))?;
+        if self.trailing_newline {
+            w.write_str(&config.newline)?;
+        }
+        Ok(())
+    }
+
+    /// Renders the tree into a new `String`, which stops at an invalid name instead of failing.
+    pub fn to_html_string(&self) -> String {
+        let mut html = String::new();
+        // Writing to a `String` doesn't fail.
+        let _ = self.render_to(&mut html);
+        html
+    }
+
+    /// Renders the tree into bytes, like for a response body, whose `len` is the `Content-Length`.
+    pub fn render_to_vec(&self) -> Vec<u8> {
+        self.to_html_string().into_bytes()
+    }
+
+    /// Renders the tree in chunks, one for each start tag, text and end tag, so a large page can be streamed
+    /// without being buffered. Joined, the chunks are the same as `render_to`.
+    ///
+    /// ```rust
+    /// use hiccup::Element;
+    ///
+    /// let list = Element::new("ul").child(Element::new("li").child(Element::text("a")));
+    /// let chunks: Vec<String> = list.render_chunks().collect();
+    ///
+    /// assert_eq!(chunks, ["<ul>", "<li>", "a", "</li>", "</ul>"]);
+    /// ```
+    pub fn render_chunks(&self) -> Chunks<'_> {
+        let mut steps = vec![Step::Start(self)];
+        if self.trailing_newline {
+            steps.insert(0, Step::Newline);
+        }
+        Chunks { steps, context: Context::default() }
+    }
+
+    fn render_with<W: Write + ?Sized>(&self, w: &mut Renderer<W>) -> fmt::Result {
+        if self.render_start(w)? {
+            if let Node::Tag { name, children, .. } = &self.node {
+                for child in children {
+                    child.render_with(w)?;
+                }
+                w.close_tag(name)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Writes the text, the empty element or the start tag, returning whether the children and end tag are still to come.
+    fn render_start<W: Write + ?Sized>(&self, w: &mut Renderer<W>) -> Result<bool, fmt::Error> {
+        match &self.node {
+            Node::Text(text) => w.text(text).map(|_| false),
+            Node::Tag { name, attrs, children } => {
+                if !is_valid_name(name) || attrs.iter().any(|(key, _)| !is_valid_name(key)) {
+                    return Err(fmt::Error);
+                }
+                w.open_tag(name)?;
+                for (key, value) in attrs {
+                    match value {
+                        Some(value) => w.attr(key, value)?,
+                        None => w.bool_attr(key)?,
+                    }
+                }
+                if children.is_empty() {
+                    return w.end_empty(name).map(|_| false);
+                }
+                w.start_children().map(|_| true)
+            }
+        }
+    }
+}
+
+/// The chunks of `Element::render_chunks`.
+pub struct Chunks<'a> {
+    // The steps left, in reverse order.
+    steps: Vec<Step<'a>>,
+    // The elements the next chunk is in, so it's escaped like in `render_to`, like the text of a `script`.
+    context: Context,
+}
+
+enum Step<'a> {
+    Start(&'a Element),
+    End(&'a str),
+    Newline,
+}
+
+impl Iterator for Chunks<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let step = self.steps.pop()?;
+        let mut chunk = String::new();
+        let mut w = Renderer::resume(&mut chunk, mem::take(&mut self.context));
+        match step {
+            Step::Start(element) => {
+                if let (Ok(true), Node::Tag { name, children, .. }) = (element.render_start(&mut w), &element.node) {
+                    self.steps.push(Step::End(name));
+                    self.steps.extend(children.iter().rev().map(Step::Start));
+                }
+            }
+            Step::End(name) => {
+                let _ = w.close_tag(name);
+            }
+            Step::Newline => {
+                let _ = w.write_str("\n");
+            }
+        }
+        self.context = w.suspend();
+        Some(chunk)
+    }
+}
+
+// As a child of the macros, like `div[(element)]`, it's rendered as markup instead of escaped text.
+// It's also `Display`, so `(&element)` is ambiguous, the child is already borrowed, like for `PreEscaped`.
+impl Render for Element {
+    fn render(&self, w: &mut dyn Write) -> fmt::Result {
+        self.render_with(&mut Renderer::new(w))
+    }
+}
+
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.render_to(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Element, PrettyConfig};
+
+    #[test]
+    fn builder_matches_macro() {
+        let tag = "section";
+        let mut out = String::new();
+
+        Element::new(tag)
+            .attr("class", "card")
+            .child(Element::new("h2").child(Element::text("Tom & Jerry")))
+            .child(Element::new("input").attr("type", "checkbox").bool_attr("checked"))
+            .child(
+                Element::new("p")
+                    .attr("title", "\"quoted\"")
+                    .child(Element::text("a "))
+                    .child(Element::new("b").child(Element::text("b"))),
+            )
+            .child(Element::new("div"))
+            .render_to(&mut out)
+            .unwrap();
+
+        assert_eq!(out, hiccup_string!(
+            section{class=>"card"}[
+                h2["Tom & Jerry"]
+                input{type=>"checkbox", checked}
+                p{title=>"\"quoted\""}["a " b["b"]]
+                div
+            ]));
+    }
+
+    #[test]
+    fn invalid_names_fail() {
+        let mut out = String::new();
+
+        assert!(Element::new("div><script>alert(1)</script").render_to(&mut out).is_err());
+        assert!(Element::new("div").attr("a b=\"c\"", "v").render_to(&mut out).is_err());
+        assert!(Element::new("div").bool_attr("a>").render_to(&mut out).is_err());
+        assert!(Element::new("ul").child(Element::new("")).render_to(&mut out).is_err());
+        assert!(!out.contains("script") && !out.contains("a b"));
+    }
+
+    #[test]
+    fn attributes_keep_insertion_order() {
+        let el = Element::new("div").attr("b", "1").attr("a", "2").bool_attr("c").attr("a0", "3");
+
+        assert_eq!(el.to_string(), "<div b=\"1\" a=\"2\" c a0=\"3\"></div>");
+    }
+
+    #[test]
+    fn render_to_any_writer() {
+        struct Bytes(Vec<u8>);
+
+        impl std::fmt::Write for Bytes {
+            fn write_str(&mut self, s: &str) -> std::fmt::Result {
+                self.0.extend_from_slice(s.as_bytes());
+                Ok(())
+            }
+        }
+
+        let el = Element::new("p").attr("class", "a&b").child(Element::text("<hi>")).child(Element::new("br"));
+        let mut bytes = Bytes(Vec::new());
+
+        el.render_to(&mut bytes).unwrap();
+
+        assert_eq!(el.to_html_string(), "<p class=\"a&amp;b\">&lt;hi&gt;<br/></p>");
+        assert_eq!(bytes.0, el.to_html_string().into_bytes());
+    }
+
+    #[test]
+    fn transform_every_text() {
+        let el = Element::new("div")
+            .attr("title", "kept")
+            .child(Element::text("hello "))
+            .child(Element::new("b").child(Element::text("world")))
+            .child(Element::new("p").child(Element::new("i").child(Element::text("<x>"))))
+            .transform_text(|s| s.to_uppercase());
+
+        assert_eq!(el.to_html_string(), "<div title=\"kept\">HELLO <b>WORLD</b><p><i>&lt;X&gt;</i></p></div>");
+    }
+
+    #[test]
+    fn render_to_bytes() {
+        let el = Element::new("p").child(Element::text("café & ☕"));
+        let html = el.to_html_string();
+
+        let bytes = el.render_to_vec();
+
+        assert_eq!(bytes, html.as_bytes());
+        assert_eq!(bytes.len(), "<p>café &amp; ☕</p>".len());
+    }
+
+    #[test]
+    fn trailing_newline() {
+        let page = Element::new("html").child(Element::new("body").with_trailing_newline(true));
+
+        assert_eq!(page.to_html_string(), "<html><body></body></html>");
+        assert_eq!(page.clone().with_trailing_newline(false).to_html_string(), "<html><body></body></html>");
+
+        let page = page.with_trailing_newline(true);
+        assert_eq!(page.to_html_string(), "<html><body></body></html>\n");
+        assert_eq!(page.to_string(), "<html><body></body></html>\n");
+        assert_eq!(page.render_chunks().collect::<String>(), "<html><body></body></html>\n");
+    }
+
+    #[test]
+    fn inspect_the_tree() {
+        let item = |text: &str| Element::new("li").child(Element::text(text));
+        let list = Element::new("ul")
+            .child(item("a"))
+            .child(Element::new("div").child(Element::new("ul").child(item("b")).child(item("c"))))
+            .child(Element::text("li"));
+
+        let items = list.find_all("li");
+
+        assert_eq!(items, [&item("a"), &item("b"), &item("c")]);
+        assert_eq!(items[1].children(), [Element::text("b")]);
+        assert_eq!(list.find_all("ul").len(), 2);
+        assert!(list.find_all("p").is_empty());
+        assert_eq!(list.tag_name(), Some("ul"));
+        assert_eq!(list.children().len(), 3);
+        assert_eq!(list.children()[2].tag_name(), None);
+        assert!(list.children()[2].children().is_empty());
+    }
+
+    #[test]
+    fn macro_child() {
+        let card = Element::new("div")
+            .attr("class", "card")
+            .child(Element::text("Tom & Jerry"))
+            .with_trailing_newline(true);
+
+        let html = hiccup_string!(main[h1["<Cards>"] (card) (card.clone()) p["end"]]);
+
+        assert_eq!(html, "<main><h1>&lt;Cards&gt;</h1><div class=\"card\">Tom &amp; Jerry</div>\
+        <div class=\"card\">Tom &amp; Jerry</div><p>end</p></main>");
+    }
+
+    #[test]
+    fn pretty_config() {
+        let page = Element::new("body")
+            .child(Element::new("div").child(Element::new("p").child(Element::text("x"))))
+            .child(Element::new("pre").child(Element::text(" a\n b")));
+        let render = |config: &PrettyConfig| {
+            let mut out = String::new();
+            page.render_pretty(&mut out, config).unwrap();
+            out
+        };
+
+        let tabs = PrettyConfig { indent: "\t".to_string(), ..PrettyConfig::default() };
+        assert_eq!(render(&tabs), "<body>\n\t<div>\n\t\t<p>x</p>\n\t</div>\n\t<pre> a\n b</pre>\n</body>");
+
+        let crlf = PrettyConfig { indent: "    ".to_string(), newline: "\r\n".to_string() };
+        assert_eq!(render(&crlf), "<body>\r\n    <div>\r\n        <p>x</p>\r\n    </div>\r\n    <pre> a\n b</pre>\r\n</body>");
+
+        let mut out = String::new();
+        let _ = hiccup_pretty!(&mut out, body[div[p["x"]] pre[" a\n b"]]);
+        assert_eq!(render(&PrettyConfig::default()), out);
+    }
+
+    #[test]
+    fn text_ignores_builder_methods() {
+        let mut out = String::new();
+
+        Element::text("<x>").attr("class", "a").child(Element::new("p")).render_to(&mut out).unwrap();
+
+        assert_eq!(out, "&lt;x&gt;");
+    }
+
+    #[test]
+    fn display_matches_render_to() {
+        let el = Element::new("ul").child(Element::new("li").child(Element::text("a < b"))).child(Element::new("br"));
+        let mut out = String::new();
+
+        el.render_to(&mut out).unwrap();
+
+        assert_eq!(format!("{}", el), out);
+        assert_eq!(format!("{}", el), "<ul><li>a &lt; b</li><br/></ul>");
+    }
+
+    #[test]
+    fn chunks_join_to_render() {
+        let mut table = Element::new("table").attr("class", "big");
+        for i in 0..3 {
+            let row = Element::new("tr").child(Element::new("td").child(Element::text(i.to_string())));
+            table = table.child(row.child(Element::new("td")));
+        }
+        let mut out = String::new();
+
+        table.render_to(&mut out).unwrap();
+        let chunks: Vec<String> = table.render_chunks().collect();
+
+        assert_eq!(chunks.concat(), out);
+        assert_eq!(chunks[..4], ["<table class=\"big\">", "<tr>", "<td>", "0"]);
+        assert_eq!(Element::text("<x>").render_chunks().collect::<Vec<_>>(), ["&lt;x&gt;"]);
+    }
+
+    #[test]
+    fn chunks_keep_the_open_elements() {
+        let page = Element::new("body")
+            .child(Element::new("script").child(Element::text("if (a < b && c) {}")))
+            .child(Element::new("style").child(Element::text("p > b { color: red }")))
+            .child(Element::new("pre").child(Element::text("\nx")))
+            .child(Element::new("p").child(Element::text("a < b")));
+
+        let chunks: Vec<String> = page.render_chunks().collect();
+
+        assert_eq!(chunks.concat(), page.to_html_string());
+        assert!(chunks.contains(&"if (a < b && c) {}".to_string()));
+        assert!(chunks.contains(&"p > b { color: red }".to_string()));
+        assert!(chunks.contains(&"\n\nx".to_string()));
+        assert!(chunks.contains(&"a &lt; b".to_string()));
+    }
+}
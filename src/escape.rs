@@ -0,0 +1,275 @@
+//! The escaping used by the macros, for text, attribute values and comments.
+//! The `_into` versions append to an existing buffer instead of allocating a new string,
+//! and return the number of bytes appended, which is more than the length of the input when something was escaped.
+//! `escape_html_fmt` writes to a `Formatter`, for `Display` impls that escape without allocating.
+
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+/// Escapes `&`, `<`, `>`, `"` and `'` so the string can be safely used as HTML text.
+/// This is what `hiccup!` uses for text content, so `"<script>"` is emitted as `&lt;script&gt;`.
+pub fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    escape_html_into(s, &mut escaped);
+    escaped
+}
+
+/// Appends `s` to `out`, escaped like `escape_html`, and returns the number of bytes appended.
+///
+/// ```rust
+/// let mut html = String::from("<p>");
+/// let written = hiccup::escape::escape_html_into("1 < 2", &mut html);
+///
+/// assert_eq!(html, "<p>1 &lt; 2");
+/// assert_eq!(written, 8);
+/// ```
+pub fn escape_html_into(s: &str, out: &mut String) -> usize {
+    escape_into(s, out, html_escape)
+}
+
+/// Writes `s` to `f`, escaped like `escape_html`, without allocating.
+///
+/// ```rust
+/// use std::fmt;
+///
+/// struct Name<'a>(&'a str);
+///
+/// impl fmt::Display for Name<'_> {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         hiccup::escape::escape_html_fmt(self.0, f)
+///     }
+/// }
+///
+/// assert_eq!(Name("Tom & Jerry").to_string(), "Tom &amp; Jerry");
+/// ```
+pub fn escape_html_fmt(s: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    escape_to(s, f, html_escape)
+}
+
+pub(crate) fn html_escape(b: u8) -> Option<&'static str> {
+    match b {
+        b'&' => Some("&amp;"),
+        b'<' => Some("&lt;"),
+        b'>' => Some("&gt;"),
+        b'"' => Some("&quot;"),
+        b'\'' => Some("&#39;"),
+        _ => None,
+    }
+}
+
+/// Escapes like `escape_html`, and writes every non-ASCII character as a numeric character reference,
+/// for the clients that don't handle UTF-8, `"café"` is escaped as `caf&#233;`.
+pub fn escape_html_ascii(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    escape_html_ascii_into(s, &mut escaped);
+    escaped
+}
+
+/// Appends `s` to `out`, escaped like `escape_html_ascii`, and returns the number of bytes appended.
+pub fn escape_html_ascii_into(s: &str, out: &mut String) -> usize {
+    let start = out.len();
+    out.reserve(s.len());
+    // Writing to a `String` doesn't fail.
+    let _ = escape_html_ascii_to(s, out);
+    out.len() - start
+}
+
+pub(crate) fn escape_html_ascii_to<W: Write + ?Sized>(s: &str, out: &mut W) -> fmt::Result {
+    let mut run = 0;
+    for (i, c) in s.char_indices().filter(|(_, c)| !c.is_ascii()) {
+        escape_to(&s[run..i], out, html_escape)?;
+        write!(out, "&#{};", u32::from(c))?;
+        run = i + c.len_utf8();
+    }
+    escape_to(&s[run..], out, html_escape)
+}
+
+/// Escapes `&`, `"`, `<` and `>` so the string can be placed inside a double quoted attribute value.
+pub fn escape_attr(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    escape_attr_into(s, &mut escaped);
+    escaped
+}
+
+/// Appends `s` to `out`, escaped like `escape_attr`, and returns the number of bytes appended.
+pub fn escape_attr_into(s: &str, out: &mut String) -> usize {
+    escape_into(s, out, attr_escape)
+}
+
+pub(crate) fn attr_escape(b: u8) -> Option<&'static str> {
+    match b {
+        b'&' => Some("&amp;"),
+        b'"' => Some("&quot;"),
+        b'<' => Some("&lt;"),
+        b'>' => Some("&gt;"),
+        _ => None,
+    }
+}
+
+/// Escapes `&`, `'`, `<` and `>` so the string can be placed inside a single quoted attribute value.
+/// The `"` are left as is, `"a"` is escaped as `"a"`.
+pub fn escape_attr_single(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    escape_attr_single_into(s, &mut escaped);
+    escaped
+}
+
+/// Appends `s` to `out`, escaped like `escape_attr_single`, and returns the number of bytes appended.
+pub fn escape_attr_single_into(s: &str, out: &mut String) -> usize {
+    escape_into(s, out, attr_single_escape)
+}
+
+pub(crate) fn attr_single_escape(b: u8) -> Option<&'static str> {
+    match b {
+        b'&' => Some("&amp;"),
+        b'\'' => Some("&#39;"),
+        b'<' => Some("&lt;"),
+        b'>' => Some("&gt;"),
+        _ => None,
+    }
+}
+
+fn escape_into(s: &str, out: &mut String, escape: fn(u8) -> Option<&'static str>) -> usize {
+    let start = out.len();
+    out.reserve(s.len());
+    // Writing to a `String` doesn't fail.
+    let _ = escape_to(s, out, escape);
+    out.len() - start
+}
+
+// Writes the runs without special characters as whole slices, which are all the text most of the times.
+// The special characters are ASCII, so they are never part of a multi-byte character.
+pub(crate) fn escape_to<W: Write + ?Sized>(s: &str, out: &mut W, escape: fn(u8) -> Option<&'static str>) -> fmt::Result {
+    let mut run = 0;
+    for (i, b) in s.bytes().enumerate() {
+        if let Some(escaped) = escape(b) {
+            if run < i {
+                out.write_str(&s[run..i])?;
+            }
+            out.write_str(escaped)?;
+            run = i + 1;
+        }
+    }
+    if run < s.len() {
+        out.write_str(&s[run..])?;
+    }
+    Ok(())
+}
+
+/// Breaks every `--` in a comment text with a space, and a trailing `-`, so it can't close the comment early.
+#[doc(hidden)]
+pub fn escape_comment(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c == '-' && escaped.ends_with('-') {
+            escaped.push(' ');
+        }
+        escaped.push(c);
+    }
+    if escaped.ends_with('-') {
+        escaped.push(' ');
+    }
+    escaped
+}
+
+/// Splits every `]]>` in a CDATA text across two sections, `]]]]><![CDATA[>`, so it can't end the section early.
+#[doc(hidden)]
+pub fn escape_cdata(s: &str) -> String {
+    s.replace("]]>", "]]]]><![CDATA[>")
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt;
+
+    use super::{
+        escape_attr, escape_attr_into, escape_attr_single, escape_attr_single_into, escape_html, escape_html_ascii,
+        escape_html_ascii_into, escape_html_fmt, escape_html_into,
+    };
+
+    #[test]
+    fn escape_each_special_char() {
+        assert_eq!(escape_html("&"), "&amp;");
+        assert_eq!(escape_html("<"), "&lt;");
+        assert_eq!(escape_html(">"), "&gt;");
+        assert_eq!(escape_html("\""), "&quot;");
+        assert_eq!(escape_html("'"), "&#39;");
+        assert_eq!(escape_html("a<b>&\"c'"), "a&lt;b&gt;&amp;&quot;c&#39;");
+    }
+
+    #[test]
+    fn escape_attr_chars() {
+        assert_eq!(escape_attr("a&b\"c<d>'"), "a&amp;b&quot;c&lt;d&gt;'");
+    }
+
+    #[test]
+    fn escape_attr_single_chars() {
+        assert_eq!(escape_attr_single("a&b\"c<d>'"), "a&amp;b\"c&lt;d&gt;&#39;");
+        assert_eq!(escape_attr_single("plain"), "plain");
+    }
+
+    #[test]
+    fn escape_empty_and_plain_strings() {
+        assert_eq!(escape_html(""), "");
+        assert_eq!(escape_attr(""), "");
+        assert_eq!(escape_html("plain text"), "plain text");
+        assert_eq!(escape_attr("plain text"), "plain text");
+    }
+
+    #[test]
+    fn escape_into_appends() {
+        let mut out = String::from("x=");
+        escape_html_into("<a>", &mut out);
+        escape_html_into("", &mut out);
+        assert_eq!(out, "x=&lt;a&gt;");
+
+        let mut out = String::from("x=");
+        escape_attr_into("\"a\" & 'b'", &mut out);
+        escape_attr_into("plain", &mut out);
+        assert_eq!(out, "x=&quot;a&quot; &amp; 'b'plain");
+    }
+
+    #[test]
+    fn escape_into_returns_the_bytes_appended() {
+        let mut out = String::from("x=");
+
+        assert_eq!(escape_html_into("plain", &mut out), 5);
+        assert_eq!(escape_html_into("a<b", &mut out), 6);
+        assert_eq!(escape_html_into("", &mut out), 0);
+        assert_eq!(escape_attr_into("\"é\"", &mut out), 14);
+        assert_eq!(escape_attr_single_into("it's", &mut out), 8);
+        assert_eq!(escape_html_ascii_into("é<", &mut out), 10);
+        assert_eq!(out, "x=plaina&lt;b&quot;é&quot;it&#39;s&#233;&lt;");
+        assert_eq!(out.len(), 2 + 5 + 6 + 14 + 8 + 10);
+    }
+
+    #[test]
+    fn escape_into_a_formatter() {
+        struct Escaped<'a>(&'a str);
+
+        impl fmt::Display for Escaped<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                escape_html_fmt(self.0, f)
+            }
+        }
+
+        assert_eq!(Escaped("<a href=\"x\">Tom & 'Jerry'</a>").to_string(), escape_html("<a href=\"x\">Tom & 'Jerry'</a>"));
+        assert_eq!(Escaped("é<ü>").to_string(), "é&lt;ü&gt;");
+        assert_eq!(Escaped("plain").to_string(), "plain");
+        assert_eq!(Escaped("").to_string(), "");
+    }
+
+    #[test]
+    fn escape_non_ascii() {
+        assert_eq!(escape_html_ascii("café <b>"), "caf&#233; &lt;b&gt;");
+        assert_eq!(escape_html_ascii("hi 👋"), "hi &#128075;");
+        assert_eq!(escape_html_ascii(""), "");
+        assert_eq!(escape_html("café 👋"), "café 👋");
+    }
+
+    #[test]
+    fn escape_multi_byte_text() {
+        assert_eq!(escape_html("é<ü>☕&'\"👋"), "é&lt;ü&gt;☕&amp;&#39;&quot;👋");
+        assert_eq!(escape_attr("ü\"a\"☕"), "ü&quot;a&quot;☕");
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+use std::io;
+
+/// Adapts a `std::io::Write` into a `std::fmt::Write`, keeping the io error that stopped the rendering.
+/// This is what `hiccup_io!` renders into.
+#[doc(hidden)]
+pub struct IoWriter<'a, W: io::Write + ?Sized> {
+    inner: &'a mut W,
+    error: Option<io::Error>,
+    // With `hiccup_io_flushing!`, the writer is flushed every time this many bytes were written since the last flush.
+    flush_every: Option<usize>,
+    unflushed: usize,
+}
+
+impl<'a, W: io::Write + ?Sized> IoWriter<'a, W> {
+    pub fn new(inner: &'a mut W) -> Self {
+        IoWriter { inner, error: None, flush_every: None, unflushed: 0 }
+    }
+
+    /// An adapter that flushes `inner` after every `every` bytes, and once more at the end.
+    pub fn flushing(inner: &'a mut W, every: usize) -> Self {
+        IoWriter { flush_every: Some(every), ..IoWriter::new(inner) }
+    }
+
+    /// Turns the result of the rendering into the io error that caused it to fail.
+    pub fn finish(self, result: fmt::Result) -> io::Result<()> {
+        match (result, self.error) {
+            (Ok(()), _) if self.flush_every.is_some() && self.unflushed > 0 => self.inner.flush(),
+            (Ok(()), _) => Ok(()),
+            (Err(_), Some(e)) => Err(e),
+            (Err(_), None) => Err(io::Error::other("formatter error")),
+        }
+    }
+
+    fn write_all(&mut self, s: &str) -> io::Result<()> {
+        self.inner.write_all(s.as_bytes())?;
+        self.unflushed += s.len();
+        match self.flush_every {
+            Some(every) if self.unflushed >= every => {
+                self.unflushed = 0;
+                self.inner.flush()
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<W: io::Write + ?Sized> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_all(s).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
@@ -1,15 +1,71 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
 /// # `hiccup!`: 
 /// * The main objective of this lib is to prevent unclosed html tags.
-/// This macro is inspired by Clojures [hiccup](https://github.com/weavejester/hiccup)
+///   This macro is inspired by Clojures [hiccup](https://github.com/weavejester/hiccup)
 /// 
 /// ## Basic usage: 
 /// 
 /// The macro `hiccup! receives a mutable string as the first argument and mutates the string to emit the HTML.
+/// Any writer works, the first argument can be a `&mut` reference to any `std::fmt::Write`, like `&mut String`, a `&mut dyn std::fmt::Write` or a generic `&mut W` where `W: std::fmt::Write + ?Sized`.
+/// It evaluates to a `std::fmt::Result`, so a failed write stops the rendering and can be propagated with `?`.
 /// The order of the elemnts is: 
 /// 1. `tag` as the first element.
 /// 2. Optional attribute inside the tag should follow the tag name as `{attribute1=>"value1 vlaue2 ... valuen", attr=>"value"}`. Also, the attributes should be inside `{...}` and separate each key value pair by `,`.
-/// The element should be written as `key=>"value"`, where key is a symbol, followed by an arrow (`=>`), and then the value as a string `"value"`.
+///    The attributes are always written in the order they're written in the macro.
+///    The element should be written as `key=>"value"`, where key is a symbol, followed by an arrow (`=>`), and then the value as a string `"value"`.
+///    Numbers and booleans are quoted like strings, `maxlength=>10` emits `maxlength="10"`.
+///    To compute the value at runtime wrap an expression in parenthesis, like `a{href=>(url), class=>"link"}`. Values are HTML escaped.
+///    Any `Display` value can be used, like numbers and custom types, `li{value=>(i)}`.
+///    Boolean attributes are written without a value, like `input{type=>"checkbox", checked}`, and only when a condition holds with `if`, like `button{disabled if busy}`.
+///    The same key can be boolean or valued, `a{download}` emits `<a download>` and `a{download=>"file.txt"}` emits `<a download="file.txt">`.
+///    Keys that aren't valid Rust identifiers, like `data-*` and `aria-*` attributes, can be written as strings, like `{"aria-label"=>"close"}`.
+///    Namespaced keys can be written as they are, like `use{xlink:href=>"#icon"}`.
+///    Rust keywords can be used as keys, like `label{for=>"name"}` and `input{type=>"text", loop}`, or written as strings, `{"type"=>"text"}`.
+///    An `Option` value after `=>?`, like `a{href=>?url}`, omits the attribute when it's `None`.
+///    A list of values after `=>@`, like `div{class=>@classes}`, is joined by spaces, skipping the empty ones.
+///    Attributes only known at runtime can be splatted from any collection of pairs, like `div{..attrs}`, in the order of the collection. A key that isn't an attribute name, like `"a b"`, fails the rendering with `fmt::Error`.
+///    The values can also be written in brackets, each with an optional condition, `div{class=>["card", "active" if is_active]}`.
+///    Styles can be written as a map, `div{style=>{color=>"red", font_weight=>(weight)}}` emits `style="color:red;font-weight:bold"`,
+///    the `_` in the names are written as `-`.
 /// 3. After (Optional) the tag name or the attributes `{...}` tou should include `[...]` that can have other tags, such as `p["text"]` or regular string values.
+///    A parenthesized expression, like `p[(user.name)]`, writes the value of the expression as escaped text.
+///    An `Option` after `?`, like `span[?label]`, writes its value when it's `Some` and nothing when it's `None`.
+///    `text!("Hello {}", name)` formats its arguments like `format!` and writes the result as escaped text.
+///    Trusted markup can be wrapped in `PreEscaped`, like `p[(PreEscaped(html))]`, to be written without escaping.
+///    An owned `String` of trusted markup can be wrapped in `Safe`, like `div[(Safe(fragment))]`.
+///    Values implementing `Render`, like reusable components, are rendered as markup, like `div[(card)]`.
+///    So are collections of them, like a `Vec`, a slice or an array, `ul[(items)]`, and `Clone` iterators of them, like `ul[(names.iter().map(|n| Item(n)))]`, one after the other.
+///    The child is already borrowed, so a component that is also `Display`, like `PreEscaped` and `Element`, is written without a `&`, `p[(bold)]`, with one `p[(&bold)]` fails with E0034, "multiple applicable items".
+///    A component renders with the macro it calls, so the modes of the caller, like `hiccup_pretty!`, `hiccup_safe_urls!`, `hiccup_squote!` or `hiccup_xml!`, don't apply inside it.
+///    The text of `script` and `style` is code, so it's written without escaping, only a `</script` or `</style` in it is written as `<\/script`.
+///    Text and tags can be mixed in any order, like `p["Hello " b["world"] "!"]`. Adjacent texts are written one after the other, so a long text can be split across lines.
+/// 4. Void elements, like `br`, `img` and `meta`, are self-closed (`<br/>`) when they have no `[...]`.
+///    Every other tag is closed explicitly, so `div` and `div[]` both emit `<div></div>`.
+///    SVG shapes, like `path`, `circle` and `rect`, are self-closed too, `svg[path{d=>"M0 0 L1 1"}]` emits `<svg><path d="M0 0 L1 1"/></svg>`.
+/// 5. A leading `doctype`, like `hiccup!(&mut html, doctype html[...])`, emits `<!DOCTYPE html>` before the document.
+/// 6. `comment["text"]` emits an HTML comment, `<!--text-->`. Any `--` in the text is broken by a space.
+///    `cond_comment["lt IE 9"][...]` wraps its children in a conditional comment, `<!--[if lt IE 9]>...<![endif]-->`, for legacy email clients.
+///    `cdata[(data)]` emits an unescaped `<![CDATA[data]]>` section for XML, a `]]>` in the data is split across two sections.
+/// 7. `if (cond) [...]` renders its children only when `cond` is true, an optional `else [...]` renders otherwise.
+///    `match (value) { Pattern => [...], ... }` renders the children of the matching arm, arms can have guards, like `n if n > 1 => [...]`.
+/// 8. `for (item in items) [...]` renders its children once for each item, like `ul[for (i in &items) [li[(i)]]]`.
+///    Loops can be nested, the inner loop can use the variables of the outer one, like `for (row in rows) [tr[for (cell in row) [td[(cell)]]]]`.
+///    `let name = (expr);` binds a value once for the siblings that follow it, like `li[let total = (a + b); b[(total)] i[(total)]]`.
+/// 9. `raw[(html)]` writes its text and expressions **without escaping**, for markup that is already safe.
+///    `entity(nbsp)` writes the `&nbsp;` character reference, the name is checked at compile time against the usual ones, like `mdash` and `copy`.
+/// 10. Classes can be written after the tag name, like `div.card.big["x"]` for `<div class="card big">x</div>`.
+///     They go before the classes of a `class` attribute, `div.card{class=>"big"}` emits `<div class="card big"></div>`, an empty or a bare `class` adds nothing.
+/// 11. The id can be written after the tag name too, like `div#main.card["x"]` for `<div id="main" class="card">x</div>`.
+///     A tag can have only one `#id`, a second one, or an `id` attribute with it, is a compile error.
+/// 12. Custom elements can have hyphens in their names, like `my-widget{size=>"big"}["x"]` for `<my-widget size="big">x</my-widget>`.
+/// 13. An attribute block after the children is merged into the one before them, `a{href=>"/"}["home"]{class=>"active"}`
+///     emits `<a href="/" class="active">home</a>`, the same as `a{href=>"/", class=>"active"}["home"]`.
+/// 14. A trailing `/` self-closes any tag, even if it isn't a void element, `my-icon{name=>"x"}/` emits `<my-icon name="x"/>`.
+/// 15. `frag[...]` renders its children without a wrapping tag, like `if (cond) [frag[dt["a"] dd["b"]]]`.
+/// 16. `meta_charset` emits `<meta charset="utf-8"/>`, another charset can be given like `meta_charset["iso-8859-1"]`.
+/// 17. `csrf(token)` emits the hidden input of a CSRF token, `<input type="hidden" name="_csrf" value="..."/>`, like `form{method=>"post"}[csrf(token) ...]`.
+/// 18. A leading `{ statements }` block runs before the tree, and what it declares is in scope for it, like `{ let rows = load(); } ul[for (row in &rows) [li[(row)]]]`.
 /// 
 /// ### Differences between Clojure and Rust Hiccup: 
 /// * [Clojure](https://github.com/weavejester/hiccup/wiki/Syntax): `[:a {:href "http://github.com"} "GitHub"]`
@@ -36,83 +92,2677 @@
 ///     assert_eq!(html,"<html><head><meta name=\"author\" content=\"Julia Naomi\"/>\
 ///     <title>Hiccup guide</title></head><body class=\"amazing hiccup guide\">\
 ///     <h1 font=\"bold\" color=\"red\">Hiccup is the best!</h1>\
-///     <p>please lookup clojure\'s hiccup for better ideas on this macro</p></body></html>");
+///     <p>please lookup clojure&#39;s hiccup for better ideas on this macro</p></body></html>");
 /// }
 /// ```
 /// 
 #[macro_export]
 macro_rules! hiccup {
-    ($w:expr, ) => (());
+    ($w:expr, $($tree:tt)*) => {
+        $crate::__hiccup!(@render $crate::Renderer::new(&mut *$w), $($tree)*)
+    };
+}
 
-    ($w:expr, $e:tt) => {{
-        use std::fmt::Write;
-        let _ = write!($w, "{}", $e);
+/// # `hiccup_io!`:
+/// Same syntax as `hiccup!`, but renders into a `std::io::Write`, like a `File`, a `TcpStream` or a `Vec<u8>`.
+/// It evaluates to a `std::io::Result<()>` with the io error that stopped the rendering.
+///
+/// ```rust
+/// use hiccup::hiccup_io;
+///
+/// let mut out: Vec<u8> = Vec::new();
+///
+/// hiccup_io!(&mut out, html[body[p["Hiccup"]]]).unwrap();
+///
+/// assert_eq!(out, b"<html><body><p>Hiccup</p></body></html>");
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! hiccup_io {
+    ($w:expr, $($tree:tt)*) => {{
+        let mut w = $crate::IoWriter::new(&mut *$w);
+        let result = $crate::hiccup!(&mut w, $($tree)*);
+        w.finish(result)
     }};
+}
 
-    ($w:expr, $tag:ident {$($key:expr => $value:expr),*}[$($inner:tt)*] $($rest:tt)*) => {{
-        use std::fmt::Write;
-        
-        let _ = write!($w, "<{}", stringify!($tag));
-        $(
-            let _ = write!($w, " {}=", stringify!($key));
-            let _ = write!($w, "{}", stringify!($value));
-        )*
-        let _ = write!($w, ">");
+/// # `hiccup_io_flushing!`:
+/// Same as `hiccup_io!`, but the writer is flushed every time the given number of bytes was written since the last flush,
+/// and once more at the end, so a large page reaches a `BufWriter`'s destination while it's rendered.
+///
+/// ```rust
+/// use std::io::BufWriter;
+/// use hiccup::hiccup_io_flushing;
+///
+/// let mut out = BufWriter::new(Vec::new());
+/// let rows = 0..1000;
+///
+/// hiccup_io_flushing!(&mut out, 8 * 1024, table[for (row in rows) [tr[td[(row)]]]]).unwrap();
+///
+/// assert!(out.buffer().is_empty());
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! hiccup_io_flushing {
+    ($w:expr, $every:expr, $($tree:tt)*) => {{
+        let mut w = $crate::IoWriter::flushing(&mut *$w, $every);
+        let result = $crate::hiccup!(&mut w, $($tree)*);
+        w.finish(result)
+    }};
+}
 
-        hiccup!($w, $($inner)*);
-        let _ = write!($w, "</{}>", stringify!($tag));
-        hiccup!($w, $($rest)*);
+/// # `hiccup_string!`:
+/// Same syntax as `hiccup!`, without the writer, and evaluates to the rendered `String`.
+///
+/// ```rust
+/// use hiccup::hiccup_string;
+///
+/// let html = hiccup_string!(html[body["hi"]]);
+///
+/// assert_eq!(html, "<html><body>hi</body></html>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_string {
+    ($($tree:tt)*) => {{
+        let mut out = $crate::alloc::string::String::new();
+        let _ = $crate::hiccup!(&mut out, $($tree)*);
+        out
     }};
+}
+
+/// # `hiccup_doc!`:
+/// Same as `hiccup_string!`, but the tree is wrapped in the document, `<!DOCTYPE html>` and an `html` with the given `lang`.
+///
+/// ```rust
+/// use hiccup::hiccup_doc;
+///
+/// let html = hiccup_doc!("en", head[title["Hi"]] body["hi"]);
+///
+/// assert_eq!(html, "<!DOCTYPE html><html lang=\"en\"><head><title>Hi</title></head><body>hi</body></html>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_doc {
+    ($lang:expr, $($tree:tt)*) => {
+        $crate::hiccup_string!(doctype html{lang=>$lang}[$($tree)*])
+    };
+}
 
-    ($w:expr, $tag:ident {$($key:expr => $value:expr),*} $($rest:tt)*) => {{
-        use std::fmt::Write;
-        
-        let _ = write!($w, "<{}", stringify!($tag));
-        $(
-            let _ = write!($w, " {}=", stringify!($key));
-            let _ = write!($w, "{}", stringify!($value));
-        )*
-        let _ = write!($w, "/>");
-        hiccup!($w, $($rest)*);
+/// # `hiccup_with_capacity!`:
+/// Same as `hiccup_string!`, but the `String` is allocated with the given capacity up front,
+/// so big pages aren't reallocated while they are rendered.
+///
+/// ```rust
+/// use hiccup::hiccup_with_capacity;
+///
+/// let html = hiccup_with_capacity!(1024, html[body["hi"]]);
+///
+/// assert_eq!(html, "<html><body>hi</body></html>");
+/// assert_eq!(html.capacity(), 1024);
+/// ```
+#[macro_export]
+macro_rules! hiccup_with_capacity {
+    ($capacity:expr, $($tree:tt)*) => {{
+        let mut out = $crate::alloc::string::String::with_capacity($capacity);
+        let _ = $crate::hiccup!(&mut out, $($tree)*);
+        out
     }};
+}
 
-    ($w:expr, $tag:ident [$($inner:tt)*] $($rest:tt)*) => {{
-        use std::fmt::Write;
-        
-        let _ = write!($w, "<{}>", stringify!($tag));
-        hiccup!($w, $($inner)*);
-        let _ = write!($w, "</{}>", stringify!($tag));
-        hiccup!($w, $($rest)*);
+/// # `hiccup_each!`:
+/// Renders every item of an `ExactSizeIterator`, like a slice or a `Vec`, into the same `String`, one fragment after the other,
+/// with a closure receiving the buffer and the item. It evaluates to a `std::fmt::Result` and stops at the first error.
+/// The buffer is reserved once, for the other items to have the size of the first one, or for the given capacity per item up front.
+///
+/// ```rust
+/// use hiccup::{hiccup, hiccup_each};
+///
+/// let mut html = String::new();
+/// let rows = ["a", "b"];
+///
+/// hiccup_each!(&mut html, &rows, |buf, row| hiccup!(buf, tr[td[(row)]])).unwrap();
+/// hiccup_each!(&mut html, &rows, 32, |buf, row| hiccup!(buf, li[(row)])).unwrap();
+///
+/// assert_eq!(html, "<tr><td>a</td></tr><tr><td>b</td></tr><li>a</li><li>b</li>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_each {
+    ($buf:expr, $items:expr, $render:expr) => {
+        $crate::render_each(&mut *$buf, $items, ::core::option::Option::None, $render)
+    };
+
+    ($buf:expr, $items:expr, $capacity:expr, $render:expr) => {
+        $crate::render_each(&mut *$buf, $items, ::core::option::Option::Some($capacity), $render)
+    };
+}
+
+/// # `hiccup_writeln!`:
+/// Same syntax as `hiccup!`, but writes a `\n` after the fragment, for documents built by appending fragments one by one.
+/// The newline is only written when the fragment was rendered.
+///
+/// ```rust
+/// use hiccup::hiccup_writeln;
+///
+/// let mut html = String::new();
+///
+/// let _ = hiccup_writeln!(&mut html, h1["Title"]);
+/// let _ = hiccup_writeln!(&mut html, p["text"]);
+///
+/// assert_eq!(html, "<h1>Title</h1>\n<p>text</p>\n");
+/// ```
+#[macro_export]
+macro_rules! hiccup_writeln {
+    ($w:expr, $($tree:tt)*) => {{
+        let w = &mut *$w;
+        $crate::hiccup!(w, $($tree)*).and_then(|()| ::core::fmt::Write::write_str(w, "\n"))
     }};
 }
 
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn basic_html() {
-        let mut out = String::new();
+/// # `hiccup_checked!`:
+/// Same syntax as `hiccup!`, but in debug builds the output is checked to have every start tag closed in order,
+/// which only `raw[...]` can break, with a `debug_assert!` that panics telling the unbalanced tag.
+/// The fragment is rendered into a `String` before being written, in release builds it's exactly `hiccup!`.
+///
+/// ```rust
+/// use hiccup::hiccup_checked;
+///
+/// let mut html = String::new();
+///
+/// let _ = hiccup_checked!(&mut html, div[raw["<b>bold</b>"] br]);
+///
+/// assert_eq!(html, "<div><b>bold</b><br/></div>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_checked {
+    ($w:expr, $($tree:tt)*) => {{
+        if cfg!(debug_assertions) {
+            let mut checked = $crate::alloc::string::String::new();
+            let result = $crate::hiccup!(&mut checked, $($tree)*);
+            if let Err(error) = $crate::check_balanced(&checked) {
+                debug_assert!(false, "hiccup: unbalanced output, {}: {}", error, checked);
+            }
+            result.and_then(|()| ::core::fmt::Write::write_str(&mut *$w, &checked))
+        } else {
+            $crate::hiccup!($w, $($tree)*)
+        }
+    }};
+}
 
-        let _ = hiccup!(&mut out,
-            html[
-                head[title["Hiccup guide"]]
-                body[h1["Hiccup is the best!"]]
-            ]);
+/// # `hiccup_pretty!`:
+/// Same syntax as `hiccup!`, but every block element goes to its own line, indented by two spaces per nesting level.
+/// Inline elements, like `a`, `b` and `span`, and text stay in the same line.
+/// Nothing is indented inside `pre` and `textarea`, so their whitespace is kept as is.
+///
+/// ```rust
+/// use hiccup::hiccup_pretty;
+///
+/// let mut html = String::new();
+///
+/// let _ = hiccup_pretty!(&mut html, html[body[p["Hiccup is the " b["best"]]]]);
+///
+/// assert_eq!(html, "<html>\n  <body>\n    <p>Hiccup is the <b>best</b></p>\n  </body>\n</html>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_pretty {
+    ($w:expr, $($tree:tt)*) => {
+        $crate::__hiccup!(@render $crate::Renderer::pretty(&mut *$w), $($tree)*)
+    };
+}
 
-            assert_eq!(out, "<html><head><title>Hiccup guide</title></head>\
-            <body><h1>Hiccup is the best!</h1></body></html>");
-    }
+/// # `hiccup_min!`:
+/// Same syntax as `hiccup!`, but every run of whitespace in text is collapsed into a single space.
+/// The text inside `pre` and `textarea` is kept as is.
+///
+/// ```rust
+/// use hiccup::hiccup_min;
+///
+/// let mut html = String::new();
+///
+/// let _ = hiccup_min!(&mut html, div[p["Hiccup   is
+///     the best"] pre["  kept\n  as is"]]);
+///
+/// assert_eq!(html, "<div><p>Hiccup is the best</p><pre>  kept\n  as is</pre></div>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_min {
+    ($w:expr, $($tree:tt)*) => {
+        $crate::__hiccup!(@render $crate::Renderer::minified(&mut *$w), $($tree)*)
+    };
+}
 
-    #[test]
-    fn attr_block() {
-        let mut out = String::new();
+/// # `hiccup_html5!`:
+/// Same syntax as `hiccup!`, but void elements are written without the trailing slash, like `<br>`, as in the HTML5 spec.
+///
+/// ```rust
+/// use hiccup::hiccup_html5;
+///
+/// let mut html = String::new();
+///
+/// let _ = hiccup_html5!(&mut html, head[meta_charset link{rel=>"icon", href=>"a.png"}]);
+///
+/// assert_eq!(html, "<head><meta charset=\"utf-8\"><link rel=\"icon\" href=\"a.png\"></head>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_html5 {
+    ($w:expr, $($tree:tt)*) => {
+        $crate::__hiccup!(@render $crate::Renderer::html5(&mut *$w), $($tree)*)
+    };
+}
 
-        let _ = hiccup!(&mut out,
-            html[
-                head[title["Hiccup guide"]]
-                body[h1{class=>"value", c=>"v"}["Hiccup is the best!"]]
-            ]);
+/// # `hiccup_kebab!`:
+/// Same syntax as `hiccup!`, but every `_` in an attribute key is written as `-`,
+/// so keys like `http-equiv` and `accept-charset` can be written as identifiers.
+/// Keys written as strings are translated too, so use `hiccup!` for the rare attributes with an underscore.
+///
+/// ```rust
+/// use hiccup::hiccup_kebab;
+///
+/// let mut html = String::new();
+///
+/// let _ = hiccup_kebab!(&mut html, meta{http_equiv=>"refresh", content=>"30"});
+///
+/// assert_eq!(html, "<meta http-equiv=\"refresh\" content=\"30\"/>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_kebab {
+    ($w:expr, $($tree:tt)*) => {
+        $crate::__hiccup!(@render $crate::Renderer::kebab(&mut *$w), $($tree)*)
+    };
+}
 
-        assert_eq!(out, "<html><head><title>Hiccup guide</title></head><body>\
-        <h1 class=\"value\" c=\"v\">Hiccup is the best!</h1></body></html>");
+/// # `hiccup_lower!`:
+/// Same syntax as `hiccup!`, but the tag names are written in lowercase, so `Div` emits `<div>`.
+///
+/// ```rust
+/// use hiccup::hiccup_lower;
+///
+/// let mut html = String::new();
+///
+/// let _ = hiccup_lower!(&mut html, Div{Class=>"x"}[BR]);
+///
+/// assert_eq!(html, "<div Class=\"x\"><br/></div>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_lower {
+    ($w:expr, $($tree:tt)*) => {
+        $crate::__hiccup!(@render $crate::Renderer::lower(&mut *$w), $($tree)*)
+    };
+}
+
+/// # `hiccup_ascii!`:
+/// Same syntax as `hiccup!`, but the non-ASCII characters of text are written as numeric character references,
+/// like `&#233;` for `é`, for the email clients that don't handle UTF-8.
+///
+/// ```rust
+/// use hiccup::hiccup_ascii;
+///
+/// let mut html = String::new();
+///
+/// let _ = hiccup_ascii!(&mut html, p["Café 👋"]);
+///
+/// assert_eq!(html, "<p>Caf&#233; &#128075;</p>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_ascii {
+    ($w:expr, $($tree:tt)*) => {
+        $crate::__hiccup!(@render $crate::Renderer::ascii(&mut *$w), $($tree)*)
+    };
+}
+
+/// # `hiccup_squote!`:
+/// Same syntax as `hiccup!`, but the attribute values are single quoted, and their `'` are escaped instead of their `"`,
+/// for values full of double quotes, like JSON or SVG data URIs.
+///
+/// ```rust
+/// use hiccup::hiccup_squote;
+///
+/// let mut html = String::new();
+///
+/// let _ = hiccup_squote!(&mut html, div.card{data_json=>r#"{"a":"it's"}"#}["hi"]);
+///
+/// assert_eq!(html, r#"<div data_json='{"a":"it&#39;s"}' class='card'>hi</div>"#);
+/// ```
+#[macro_export]
+macro_rules! hiccup_squote {
+    ($w:expr, $($tree:tt)*) => {
+        $crate::__hiccup!(@render $crate::Renderer::squote(&mut *$w), $($tree)*)
+    };
+}
+
+/// # `hiccup_safe_urls!`:
+/// Same syntax as `hiccup!`, but the `href`, `src`, `xlink:href`, `action` and `formaction` values, whatever the case of the key,
+/// with a script scheme, like `javascript:alert(1)`, are written as `#`, whatever their case and leading whitespace, for URLs that come from users.
+/// Only the tree of the macro is checked: a `Render` component renders with its own `hiccup!`,
+/// so the URLs it writes aren't, use `hiccup_safe_urls!` inside the component too.
+///
+/// ```rust
+/// use hiccup::hiccup_safe_urls;
+///
+/// let mut html = String::new();
+/// let url = " JavaScript:alert(1)";
+///
+/// let _ = hiccup_safe_urls!(&mut html, a{href=>(url)}["profile"]);
+///
+/// assert_eq!(html, "<a href=\"#\">profile</a>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_safe_urls {
+    ($w:expr, $($tree:tt)*) => {
+        $crate::__hiccup!(@render $crate::Renderer::safe_urls(&mut *$w), $($tree)*)
+    };
+}
+
+/// # `hiccup_with_void!`:
+/// Same syntax as `hiccup!`, after the list of the elements to self-close, which replaces the HTML void elements,
+/// for SGML formats with other empty elements. A listed element is self-closed with or without empty `[]`.
+///
+/// ```rust
+/// use hiccup::hiccup_with_void;
+///
+/// let mut out = String::new();
+///
+/// let _ = hiccup_with_void!(&["pb", "anchor"], &mut out, chapter[pb{n=>1} para["text"] anchor[] br]);
+///
+/// assert_eq!(out, "<chapter><pb n=\"1\"/><para>text</para><anchor/><br></br></chapter>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_with_void {
+    ($void:expr, $w:expr, $($tree:tt)*) => {
+        $crate::__hiccup!(@render $crate::Renderer::with_void(&mut *$w, $void), $($tree)*)
+    };
+}
+
+/// # `hiccup_xml!`:
+/// Same syntax as `hiccup!`, but for XML documents, like RSS feeds and sitemaps.
+/// Every element without children is self-closed, `foo` and `foo[]` both emit `<foo/>`, no matter if it's an HTML void element.
+/// A leading `xml` emits the `<?xml version="1.0" encoding="UTF-8"?>` declaration, unless it's followed by `{` or `[`,
+/// like `xml{version=>"1"}[...]`, which is a root element named `xml`. A leading `doctype` is an error, it's the HTML doctype.
+///
+/// ```rust
+/// use hiccup::hiccup_xml;
+///
+/// let mut xml = String::new();
+///
+/// let _ = hiccup_xml!(&mut xml, xml rss{version=>"2.0"}[channel[title["News"] docs[]]]);
+///
+/// assert_eq!(xml, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+/// <rss version=\"2.0\"><channel><title>News</title><docs/></channel></rss>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_xml {
+    ($w:expr, $($tree:tt)*) => {
+        $crate::__hiccup!(@render $crate::Renderer::xml(&mut *$w), @xml $($tree)*)
+    };
+}
+
+/// # `hiccup_strict!`:
+/// Same as `hiccup!`, but a tag that isn't an HTML5 (or inline SVG) element is a compile error,
+/// so typos like `dvi` or `spam` don't reach the page. Custom elements, which have hyphens in their names, are allowed.
+/// A block element inside a `p`, like `p[div["x"]]`, is a compile error too, browsers would close the `p` before it.
+/// So is a literal `role` that isn't an ARIA role, like `role=>"buton"`.
+///
+/// ```rust
+/// use hiccup::hiccup_strict;
+///
+/// let mut html = String::new();
+///
+/// let _ = hiccup_strict!(&mut html, div[span["ok"] my-widget["custom"]]);
+///
+/// assert_eq!(html, "<div><span>ok</span><my-widget>custom</my-widget></div>");
+/// ```
+///
+/// ```compile_fail
+/// use hiccup::hiccup_strict;
+///
+/// let mut html = String::new();
+///
+/// let _ = hiccup_strict!(&mut html, dvi["typo"]);
+/// ```
+#[macro_export]
+macro_rules! hiccup_strict {
+    ($w:expr, $($tree:tt)*) => {{
+        $crate::__hiccup_strict!(@document $($tree)*);
+        $crate::hiccup!($w, $($tree)*)
+    }};
+}
+
+/// # `head_defaults!`:
+/// The usual document `<head>`, with the charset, the viewport and the title, as a component for the other macros.
+/// The title can be any expression, it's escaped like a text child.
+/// It's HTML-only: like any component it's rendered as HTML whatever the macro around it,
+/// so inside `hiccup_xml!` its `meta` elements aren't self-closed.
+///
+/// ```rust
+/// use hiccup::{head_defaults, hiccup};
+///
+/// let mut html = String::new();
+///
+/// let _ = hiccup!(&mut html, html[(head_defaults!("Hiccup")) body["hi"]]);
+///
+/// assert_eq!(html, "<html><head><meta charset=\"utf-8\">\
+/// <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+/// <title>Hiccup</title></head><body>hi</body></html>");
+/// ```
+#[macro_export]
+macro_rules! head_defaults {
+    ($title:expr) => {
+        $crate::HeadDefaults($title)
+    };
+}
+
+/// # `hiccup_fn!`:
+/// Defines a function that renders a tree into the writer of its first parameter, a lightweight partial,
+/// `card(buf: &mut impl Write, title: &str) -> fmt::Result`.
+/// It's embedded in another tree with `Partial`, `div[(Partial(|w| card(w, "a")))]`, which writes it into the same writer.
+///
+/// ```rust
+/// use hiccup::{hiccup, hiccup_fn, Partial};
+///
+/// hiccup_fn!(fn card(buf, title: &str, likes: u32) {
+///     div.card[h2[(title)] span[(likes)]]
+/// });
+///
+/// let mut html = String::new();
+///
+/// let _ = card(&mut html, "Hiccup", 3);
+/// let _ = hiccup!(&mut html, main[(Partial(|w| card(w, "Rust", 1)))]);
+///
+/// assert_eq!(html, "<div class=\"card\"><h2>Hiccup</h2><span>3</span></div>\
+/// <main><div class=\"card\"><h2>Rust</h2><span>1</span></div></main>");
+/// ```
+#[macro_export]
+macro_rules! hiccup_fn {
+    ($vis:vis fn $name:ident ($buf:ident $(, $arg:ident : $ty:ty)* $(,)?) { $($tree:tt)* }) => {
+        $vis fn $name($buf: &mut (impl ::core::fmt::Write + ?Sized) $(, $arg: $ty)*) -> ::core::fmt::Result {
+            $crate::hiccup!($buf, $($tree)*)
+        }
+    };
+}
+
+// Walks the tree like `__hiccup!`, without rendering it, checking the tags at compile time.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __hiccup_strict {
+    (@document { $($setup:tt)* } $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@document $($rest)*);
+    };
+
+    (@document doctype $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (@document $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (@custom - $part:ident $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@custom $($rest)*);
+    };
+
+    // A namespaced key, like `xlink:role`, isn't the ARIA `role`.
+    (@attrs $ns:ident : $key:ident $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@attrs $($rest)*);
+    };
+
+    (@attrs role => $role:literal $(, $($rest:tt)*)?) => {
+        $crate::__hiccup_strict!(@attrs "role" => $role $(, $($rest)*)?);
+    };
+
+    (@attrs "role" => $role:literal $(, $($rest:tt)*)?) => {
+        const _: () = assert!($crate::is_known_role($role), concat!("hiccup: unknown ARIA role `", $role, "`"));
+        $crate::__hiccup_strict!(@attrs $($($rest)*)?);
+    };
+
+    (@attrs $other:tt $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@attrs $($rest)*);
+    };
+
+    (@attrs) => {};
+
+    (@custom $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@element $($rest)*);
+    };
+
+    (@element # $id:ident $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@element $($rest)*);
+    };
+
+    (@element . $class:ident $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@element $($rest)*);
+    };
+
+    (@element {$($attrs:tt)*} $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@attrs $($attrs)*);
+        $crate::__hiccup_strict!(@element $($rest)*);
+    };
+
+    (@element / $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (@element [$($inner:tt)*] {$($attrs:tt)*} $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@attrs $($attrs)*);
+        $crate::__hiccup_strict!(@element [$($inner)*] $($rest)*);
+    };
+
+    (@element [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($inner)*);
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (@element $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (@p # $id:ident $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@p $($rest)*);
+    };
+
+    (@p . $class:ident $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@p $($rest)*);
+    };
+
+    (@p {$($attrs:tt)*} $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@attrs $($attrs)*);
+        $crate::__hiccup_strict!(@p $($rest)*);
+    };
+
+    (@p [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($inner)*);
+        $crate::__hiccup_strict!(@element [$($inner)*] $($rest)*);
+    };
+
+    (@p $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@element $($rest)*);
+    };
+
+    // Everything inside a `p`, at any depth, is checked not to be a block element.
+    (@in_p) => {};
+
+    (@in_p if ($cond:expr) [$($then:tt)*] else [$($otherwise:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($then)* $($otherwise)* $($rest)*);
+    };
+
+    (@in_p if ($cond:expr) [$($then:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($then)* $($rest)*);
+    };
+
+    (@in_p for ($item:pat in $iter:expr) [$($body:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($body)* $($rest)*);
+    };
+
+    (@in_p match ($value:expr) { $($($pat:pat)|+ $(if $guard:expr)? => [$($body:tt)*]),+ $(,)? } $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($($body)*)+ $($rest)*);
+    };
+
+    (@in_p let $name:pat = ($value:expr); $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($rest)*);
+    };
+
+    (@in_p frag [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($inner)* $($rest)*);
+    };
+
+    (@in_p cond_comment [$cond:literal] [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($inner)* $($rest)*);
+    };
+
+    (@in_p raw [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($rest)*);
+    };
+
+    (@in_p comment [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($rest)*);
+    };
+
+    (@in_p cdata [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($rest)*);
+    };
+
+    (@in_p text ! ($($args:tt)*) $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($rest)*);
+    };
+
+    (@in_p ? $value:tt $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($rest)*);
+    };
+
+    (@in_p entity ($name:ident) $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($rest)*);
+    };
+
+    (@in_p csrf ($token:expr) $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($rest)*);
+    };
+
+    (@in_p $tag:ident - $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p_element - $($rest)*);
+    };
+
+    (@in_p $tag:ident $($rest:tt)*) => {
+        const _: () = assert!(
+            !$crate::is_block_tag(stringify!($tag)),
+            concat!("hiccup: `", stringify!($tag), "` can't be inside a `p`, which only holds phrasing content")
+        );
+        $crate::__hiccup_strict!(@in_p_element $($rest)*);
+    };
+
+    // Texts, expressions and the tokens left by the other tags, which are reported by `__hiccup!`.
+    (@in_p $other:tt $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($rest)*);
+    };
+
+    (@in_p_element - $part:ident $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p_element $($rest)*);
+    };
+
+    (@in_p_element # $id:ident $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p_element $($rest)*);
+    };
+
+    (@in_p_element . $class:ident $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p_element $($rest)*);
+    };
+
+    (@in_p_element {$($attrs:tt)*} $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p_element $($rest)*);
+    };
+
+    (@in_p_element [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($inner)*);
+        $crate::__hiccup_strict!(@in_p_element $($rest)*);
+    };
+
+    (@in_p_element / $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($rest)*);
+    };
+
+    (@in_p_element $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@in_p $($rest)*);
+    };
+
+    () => {};
+
+    (($e:expr) $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (? $value:tt $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    ($e:literal $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (if ($cond:expr) [$($then:tt)*] else [$($otherwise:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($then)*);
+        $crate::__hiccup_strict!($($otherwise)*);
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (if ($cond:expr) [$($then:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($then)*);
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (for ($item:pat in $iter:expr) [$($body:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($body)*);
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (let $name:pat = ($value:expr); $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (match ($value:expr) { $($($pat:pat)|+ $(if $guard:expr)? => [$($body:tt)*]),+ $(,)? } $($rest:tt)*) => {
+        $( $crate::__hiccup_strict!($($body)*); )+
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (raw [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (comment [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (cdata [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (cond_comment [$cond:literal] [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($inner)*);
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (entity ($name:ident) $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (text ! ($($args:tt)*) $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (frag [$($inner:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($inner)*);
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (meta_charset [$($charset:tt)*] $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (meta_charset $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    (csrf ($token:expr) $($rest:tt)*) => {
+        $crate::__hiccup_strict!($($rest)*);
+    };
+
+    ($tag:ident - $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@custom - $($rest)*);
+    };
+
+    (p $($rest:tt)*) => {
+        $crate::__hiccup_strict!(@p $($rest)*);
+    };
+
+    ($tag:ident $($rest:tt)*) => {
+        const _: () = assert!(
+            $crate::is_known_tag(stringify!($tag)),
+            concat!("hiccup: unknown tag `", stringify!($tag), "`, custom elements need a hyphen in their name")
+        );
+        $crate::__hiccup_strict!(@element $($rest)*);
+    };
+
+    // Reported by `__hiccup!`.
+    ($unexpected:tt $($rest:tt)*) => {};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __hiccup {
+    // `hiccup_xml!` marks its tree with `@xml`, after the setup block, so only it reads a leading `xml` as the declaration.
+    (@render $renderer:expr, @xml { $($setup:tt)* } $($tree:tt)*) => {
+        $crate::__hiccup!(@render $renderer, { $($setup)* } @xml $($tree)*)
+    };
+
+    (@render $renderer:expr, { $($setup:tt)* } $($tree:tt)*) => {
+        (|| -> ::core::fmt::Result {
+            $($setup)*
+            let mut w = $renderer;
+            $crate::__hiccup!(w, @document $($tree)*);
+            Ok(())
+        })()
+    };
+
+    (@render $renderer:expr, $($tree:tt)*) => {
+        (|| -> ::core::fmt::Result {
+            let mut w = $renderer;
+            $crate::__hiccup!(w, @document $($tree)*);
+            Ok(())
+        })()
+    };
+
+    (@style_key $key:ident) => (stringify!($key));
+    (@style_key $key:literal) => ($key);
+
+    ($w:expr, @document doctype $($rest:tt)*) => {{
+        use ::core::fmt::Write;
+        write!($w, "<!DOCTYPE html>")?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    // An `xml` followed by what follows a tag name is the root element, like `xml{version=>"1"}[...]`.
+    ($w:expr, @document @xml $name:ident $next:tt $($rest:tt)*) => {
+        $crate::__hiccup!($w, @xml_document [$name] $next [$name $next $($rest)*]);
+    };
+
+    ($w:expr, @document @xml xml) => ($crate::__hiccup!($w, @xml_document [xml] , [xml]));
+    ($w:expr, @document @xml doctype) => ($crate::__hiccup!($w, @xml_document [doctype] , []));
+
+    ($w:expr, @document @xml $($rest:tt)*) => {
+        $crate::__hiccup!($w, $($rest)*);
+    };
+
+    ($w:expr, @xml_document [doctype] $next:tt [$($tree:tt)*]) => {
+        compile_error!("hiccup: `doctype` is the HTML doctype, `hiccup_xml!` can't write it");
+    };
+
+    ($w:expr, @xml_document [$name:ident] {$($x:tt)*} [$($tree:tt)*]) => ($crate::__hiccup!($w, $($tree)*));
+    ($w:expr, @xml_document [$name:ident] [$($x:tt)*] [$($tree:tt)*]) => ($crate::__hiccup!($w, $($tree)*));
+    ($w:expr, @xml_document [$name:ident] / [$($tree:tt)*]) => ($crate::__hiccup!($w, $($tree)*));
+    ($w:expr, @xml_document [$name:ident] . [$($tree:tt)*]) => ($crate::__hiccup!($w, $($tree)*));
+    ($w:expr, @xml_document [$name:ident] # [$($tree:tt)*]) => ($crate::__hiccup!($w, $($tree)*));
+    ($w:expr, @xml_document [$name:ident] - [$($tree:tt)*]) => ($crate::__hiccup!($w, $($tree)*));
+
+    ($w:expr, @xml_document [xml] $next:tt [xml $($tree:tt)*]) => {{
+        use ::core::fmt::Write;
+        write!($w, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        $crate::__hiccup!($w, $($tree)*);
+    }};
+
+    ($w:expr, @xml_document [$name:ident] $next:tt [$($tree:tt)*]) => ($crate::__hiccup!($w, $($tree)*));
+
+    ($w:expr, @document $($rest:tt)*) => {
+        $crate::__hiccup!($w, $($rest)*);
+    };
+
+    ($w:expr, @attrs) => (());
+
+    // Left by merging an attribute block written after the children, `{a=>1}[...]{b=>2}` is `{a=>1, , b=>2}[...]`.
+    ($w:expr, @attrs , $($rest:tt)*) => {
+        $crate::__hiccup!($w, @attrs $($rest)*);
+    };
+
+    ($w:expr, @attrs .. $attrs:expr $(, $($rest:tt)*)?) => {{
+        $w.attrs($attrs)?;
+        $crate::__hiccup!($w, @attrs $($($rest)*)?);
+    }};
+
+    ($w:expr, @attrs $key:tt => $(, $($rest:tt)*)?) => {
+        compile_error!(concat!("hiccup: expected `=> value` after attribute key `", stringify!($key), "`"));
+    };
+
+    ($w:expr, @attrs $prefix:ident : $key:ident $($rest:tt)*) => {
+        $crate::__hiccup!($w, @attr (concat!(stringify!($prefix), ":", stringify!($key))) $($rest)*);
+    };
+
+    ($w:expr, @attrs $key:ident $($rest:tt)*) => {
+        $crate::__hiccup!($w, @attr (stringify!($key)) $($rest)*);
+    };
+
+    ($w:expr, @attrs $key:literal $($rest:tt)*) => {
+        $crate::__hiccup!($w, @attr $key $($rest)*);
+    };
+
+    ($w:expr, @attr $key:tt => ? $value:tt $(, $($rest:tt)*)?) => {{
+        if let Some(value) = &$value {
+            $w.attr($key, value)?;
+        }
+        $crate::__hiccup!($w, @attrs $($($rest)*)?);
+    }};
+
+    ($w:expr, @attr $key:tt => @ $values:tt $(, $($rest:tt)*)?) => {{
+        $w.list_attr($key, $values)?;
+        $crate::__hiccup!($w, @attrs $($($rest)*)?);
+    }};
+
+    ($w:expr, @attr $key:tt => [$($value:tt $(if $cond:expr)?),* $(,)?] $(, $($rest:tt)*)?) => {{
+        $w.list_attr($key, [$(
+            if true $(&& $cond)? { &$value as &dyn ::core::fmt::Display } else { &"" }
+        ),*])?;
+        $crate::__hiccup!($w, @attrs $($($rest)*)?);
+    }};
+
+    ($w:expr, @attr $key:tt => { $($name:tt => $value:expr),* $(,)? } $(, $($rest:tt)*)?) => {{
+        $w.style_attr($key, &[$( ($crate::__hiccup!(@style_key $name), &$value as &dyn ::core::fmt::Display) ),*])?;
+        $crate::__hiccup!($w, @attrs $($($rest)*)?);
+    }};
+
+    ($w:expr, @attr $key:tt => $value:expr $(, $($rest:tt)*)?) => {{
+        $w.attr($key, &$value)?;
+        $crate::__hiccup!($w, @attrs $($($rest)*)?);
+    }};
+
+    ($w:expr, @attr $key:tt if $cond:expr $(, $($rest:tt)*)?) => {{
+        if $cond {
+            $w.bool_attr($key)?;
+        }
+        $crate::__hiccup!($w, @attrs $($($rest)*)?);
+    }};
+
+    ($w:expr, @attr $key:tt $(, $($rest:tt)*)?) => {{
+        $w.bool_attr($key)?;
+        $crate::__hiccup!($w, @attrs $($($rest)*)?);
+    }};
+
+    // A value followed by anything but `,`, like `{class=>"a" id=>"b"}`.
+    ($w:expr, @attr (stringify!($key:ident)) => $value:tt $($rest:tt)+) => {
+        compile_error!(concat!("hiccup: missing `,` after the value of attribute `", stringify!($key), "`"));
+    };
+
+    ($w:expr, @attr $key:literal => $value:tt $($rest:tt)+) => {
+        compile_error!(concat!("hiccup: missing `,` after the value of attribute `", $key, "`"));
+    };
+
+    ($w:expr, @attr (stringify!($key:ident)) $($rest:tt)*) => {
+        compile_error!(concat!(
+            "hiccup: expected `=> value`, `,` or the end of the attributes after attribute key `", stringify!($key), "`"
+        ));
+    };
+
+    ($w:expr, @attr $key:tt $($rest:tt)*) => {
+        compile_error!(concat!(
+            "hiccup: expected `=> value`, `,` or the end of the attributes after attribute key `", $key, "`"
+        ));
+    };
+
+    ($w:expr, @tag [$($name:ident)+] [] [] - $next:ident $($rest:tt)*) => {
+        $crate::__hiccup!($w, @tag [$($name)+ $next] [] [] $($rest)*);
+    };
+
+    ($w:expr, @tag [$($name:ident)+] [] [$($class:ident)*] # $id:ident $($rest:tt)*) => {
+        $crate::__hiccup!($w, @tag [$($name)+] [$id] [$($class)*] $($rest)*);
+    };
+
+    ($w:expr, @tag [$name:ident $($more:ident)*] [$id:ident] [$($class:ident)*] # $($rest:tt)*) => {
+        compile_error!(concat!("hiccup: `", stringify!($name) $(, "-", stringify!($more))*, "` can have only one `#id`"));
+    };
+
+    ($w:expr, @tag [$($name:ident)+] [$($id:ident)?] [$($class:ident)*] . $next:ident $($rest:tt)*) => {
+        $crate::__hiccup!($w, @tag [$($name)+] [$($id)?] [$($class)* $next] $($rest)*);
+    };
+
+    ($w:expr, @tag [$name:ident $($more:ident)*] [$($id:ident)?] [$($class:ident)*] $($rest:tt)*) => {{
+        $crate::__hiccup!(@one_id [$($id)?] [$name $($more)*] $($rest)*);
+        $( $w.id_shorthand(stringify!($id)); )?
+        $w.class_shorthand(concat!($(stringify!($class), " "),*));
+        $crate::__hiccup!($w, @element (concat!(stringify!($name) $(, "-", stringify!($more))*)) $($rest)*);
+    }};
+
+    // The `#id` shorthand can't be given with an `id` attribute too, in any of the attribute blocks of the element.
+    (@one_id [$id:ident] $name:tt {$($attrs:tt)*} $($rest:tt)*) => {
+        $crate::__hiccup!(@one_id_attrs $name $($attrs)*);
+        $crate::__hiccup!(@one_id [$id] $name $($rest)*);
+    };
+
+    (@one_id [$id:ident] $name:tt [$($inner:tt)*] {$($attrs:tt)*} $($rest:tt)*) => {
+        $crate::__hiccup!(@one_id_attrs $name $($attrs)*);
+        $crate::__hiccup!(@one_id [$id] $name [$($inner)*] $($rest)*);
+    };
+
+    (@one_id [$($id:ident)?] $name:tt $($rest:tt)*) => {};
+
+    (@one_id_attrs $name:tt id => $($rest:tt)*) => {
+        $crate::__hiccup!(@one_id_attrs $name "id" => $($rest)*);
+    };
+
+    (@one_id_attrs [$name:ident $($more:ident)*] "id" => $($rest:tt)*) => {
+        compile_error!(concat!("hiccup: `", stringify!($name) $(, "-", stringify!($more))*, "` can have only one `#id`"));
+    };
+
+    (@one_id_attrs $name:tt $other:tt $($rest:tt)*) => {
+        $crate::__hiccup!(@one_id_attrs $name $($rest)*);
+    };
+
+    (@one_id_attrs $name:tt) => {};
+
+    ($w:expr, @element $tag:tt {$($attrs:tt)*} / $($rest:tt)*) => {{
+        $w.open_tag($tag)?;
+        $crate::__hiccup!($w, @attrs $($attrs)*);
+        $w.self_close()?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, @element $tag:tt / $($rest:tt)*) => {{
+        $w.open_tag($tag)?;
+        $w.self_close()?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, @element $tag:tt [$($inner:tt)*] {$($more:tt)*} $($rest:tt)*) => {
+        $crate::__hiccup!($w, @element $tag {$($more)*}[$($inner)*] $($rest)*);
+    };
+
+    ($w:expr, @element $tag:tt {$($attrs:tt)*}[$($inner:tt)*] {$($more:tt)*} $($rest:tt)*) => {
+        $crate::__hiccup!($w, @element $tag {$($attrs)*, $($more)*}[$($inner)*] $($rest)*);
+    };
+
+    ($w:expr, @element $tag:tt {$($attrs:tt)*}[] $($rest:tt)*) => {{
+        $w.open_tag($tag)?;
+        $crate::__hiccup!($w, @attrs $($attrs)*);
+        $w.end_empty_children($tag)?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, @element $tag:tt {$($attrs:tt)*}[$($inner:tt)*] $($rest:tt)*) => {{
+        $w.open_tag($tag)?;
+        $crate::__hiccup!($w, @attrs $($attrs)*);
+        $w.start_children()?;
+        $crate::__hiccup!($w, $($inner)*);
+        $w.close_tag($tag)?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, @element $tag:tt {$($attrs:tt)*} $($rest:tt)*) => {{
+        $w.open_tag($tag)?;
+        $crate::__hiccup!($w, @attrs $($attrs)*);
+        $w.end_empty($tag)?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, @element $tag:tt [] $($rest:tt)*) => {{
+        $w.open_tag($tag)?;
+        $w.end_empty_children($tag)?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, @element $tag:tt [$($inner:tt)*] $($rest:tt)*) => {{
+        $w.open_tag($tag)?;
+        $w.start_children()?;
+        $crate::__hiccup!($w, $($inner)*);
+        $w.close_tag($tag)?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, @element $tag:tt $($rest:tt)*) => {{
+        $w.open_tag($tag)?;
+        $w.end_empty($tag)?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, ) => (());
+
+    ($w:expr, doctype $($rest:tt)*) => {
+        compile_error!("hiccup: `doctype` is only valid as the first element");
+    };
+
+    ($w:expr, ($e:expr) $($rest:tt)*) => {{
+        #[allow(unused_imports)]
+        use $crate::{CollectionKind as _, IterKind as _, RenderKind as _, TextKind as _};
+        match &$e {
+            value => value.__hiccup_kind().render(value, &mut $w)?,
+        }
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, ? $value:tt $($rest:tt)*) => {{
+        if let Some(value) = &$value {
+            $crate::__hiccup!($w, (value));
+        }
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, $e:literal $($rest:tt)*) => {{
+        $w.text(&$e)?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, if ($cond:expr) [$($then:tt)*] else [$($otherwise:tt)*] $($rest:tt)*) => {{
+        if $cond {
+            $crate::__hiccup!($w, $($then)*);
+        } else {
+            $crate::__hiccup!($w, $($otherwise)*);
+        }
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, if ($cond:expr) [$($then:tt)*] $($rest:tt)*) => {{
+        if $cond {
+            $crate::__hiccup!($w, $($then)*);
+        }
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, for ($item:pat in $iter:expr) [$($body:tt)*] $($rest:tt)*) => {{
+        for $item in $iter {
+            $crate::__hiccup!($w, $($body)*);
+        }
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, let $name:pat = ($value:expr); $($rest:tt)*) => {{
+        let $name = $value;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, match ($value:expr) { $($($pat:pat)|+ $(if $guard:expr)? => [$($body:tt)*]),+ $(,)? } $($rest:tt)*) => {{
+        match $value {
+            $( $($pat)|+ $(if $guard)? => {
+                $crate::__hiccup!($w, $($body)*);
+            } )+
+        }
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, raw [$($inner:tt)*] $($rest:tt)*) => {{
+        use ::core::fmt::Write;
+        $( write!($w, "{}", $inner)?; )*
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, comment [$($inner:tt)*] $($rest:tt)*) => {{
+        use ::core::fmt::Write;
+
+        let mut text = $crate::alloc::string::String::new();
+        $( write!(text, "{}", $inner)?; )*
+        write!($w, "<!--{}-->", $crate::escape_comment(&text))?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, cond_comment [$cond:literal] [$($inner:tt)*] $($rest:tt)*) => {{
+        use ::core::fmt::Write;
+        write!($w, concat!("<!--[if ", $cond, "]>"))?;
+        $crate::__hiccup!($w, $($inner)*);
+        write!($w, "<![endif]-->")?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, cdata [$($inner:tt)*] $($rest:tt)*) => {{
+        use ::core::fmt::Write;
+
+        let mut text = $crate::alloc::string::String::new();
+        $( write!(text, "{}", $inner)?; )*
+        write!($w, "<![CDATA[{}]]>", $crate::escape_cdata(&text))?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, entity ($name:ident) $($rest:tt)*) => {{
+        use ::core::fmt::Write;
+        const _: () = assert!(
+            $crate::is_known_entity(stringify!($name)),
+            concat!("hiccup: unknown entity `", stringify!($name), "`")
+        );
+        write!($w, concat!("&", stringify!($name), ";"))?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, text ! ($($args:tt)*) $($rest:tt)*) => {{
+        $w.text(&format_args!($($args)*))?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, frag [$($inner:tt)*] $($rest:tt)*) => {{
+        $crate::__hiccup!($w, $($inner)*);
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, meta_charset [$charset:expr] $($rest:tt)*) => {{
+        $w.open_tag("meta")?;
+        $w.attr("charset", &$charset)?;
+        $w.end_empty("meta")?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, meta_charset $($rest:tt)*) => {
+        $crate::__hiccup!($w, meta_charset["utf-8"] $($rest)*);
+    };
+
+    ($w:expr, csrf ($token:expr) $($rest:tt)*) => {{
+        $w.open_tag("input")?;
+        $w.attr("type", "hidden")?;
+        $w.attr("name", "_csrf")?;
+        $w.attr("value", &$token)?;
+        $w.end_empty("input")?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, $tag:ident . $($rest:tt)*) => {
+        $crate::__hiccup!($w, @tag [$tag] [] [] . $($rest)*);
+    };
+
+    ($w:expr, $tag:ident # $($rest:tt)*) => {
+        $crate::__hiccup!($w, @tag [$tag] [] [] # $($rest)*);
+    };
+
+    ($w:expr, $tag:ident - $($rest:tt)*) => {
+        $crate::__hiccup!($w, @tag [$tag] [] [] - $($rest)*);
+    };
+
+    ($w:expr, $tag:ident {$($attrs:tt)*} / $($rest:tt)*) => {
+        $crate::__hiccup!($w, @element (stringify!($tag)) {$($attrs)*} / $($rest)*);
+    };
+
+    ($w:expr, $tag:ident / $($rest:tt)*) => {
+        $crate::__hiccup!($w, @element (stringify!($tag)) / $($rest)*);
+    };
+
+    // An attribute block after the children is merged into the one before them.
+    ($w:expr, $tag:ident [$($inner:tt)*] {$($more:tt)*} $($rest:tt)*) => {
+        $crate::__hiccup!($w, $tag {$($more)*}[$($inner)*] $($rest)*);
+    };
+
+    ($w:expr, $tag:ident {$($attrs:tt)*}[$($inner:tt)*] {$($more:tt)*} $($rest:tt)*) => {
+        $crate::__hiccup!($w, $tag {$($attrs)*, $($more)*}[$($inner)*] $($rest)*);
+    };
+
+    ($w:expr, $tag:ident {$($attrs:tt)*}[] $($rest:tt)*) => {{
+        $w.open_tag(stringify!($tag))?;
+        $crate::__hiccup!($w, @attrs $($attrs)*);
+        $w.end_empty_children(stringify!($tag))?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, $tag:ident {$($attrs:tt)*}[$($inner:tt)*] $($rest:tt)*) => {{
+        $w.open_tag(stringify!($tag))?;
+        $crate::__hiccup!($w, @attrs $($attrs)*);
+        $w.start_children()?;
+        $crate::__hiccup!($w, $($inner)*);
+        $w.close_tag(stringify!($tag))?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, $tag:ident {$($attrs:tt)*} $($rest:tt)*) => {{
+        $w.open_tag(stringify!($tag))?;
+        $crate::__hiccup!($w, @attrs $($attrs)*);
+        $w.end_empty(stringify!($tag))?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, $tag:ident [] $($rest:tt)*) => {{
+        $w.open_tag(stringify!($tag))?;
+        $w.end_empty_children(stringify!($tag))?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, $tag:ident [$($inner:tt)*] $($rest:tt)*) => {{
+        $w.open_tag(stringify!($tag))?;
+        $w.start_children()?;
+        $crate::__hiccup!($w, $($inner)*);
+        $w.close_tag(stringify!($tag))?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, $tag:ident $($rest:tt)*) => {{
+        $w.open_tag(stringify!($tag))?;
+        $w.end_empty(stringify!($tag))?;
+        $crate::__hiccup!($w, $($rest)*);
+    }};
+
+    ($w:expr, $unexpected:tt $($rest:tt)*) => {
+        compile_error!(concat!(
+            "hiccup: unexpected `", stringify!($unexpected), "`, expected a tag, a text or a parenthesized expression"
+        ));
+    };
+}
+
+#[doc(hidden)]
+pub extern crate alloc;
+
+mod check;
+mod element;
+pub mod escape;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "axum")]
+mod page;
+pub mod prelude;
+mod render;
+mod strict;
+
+#[doc(hidden)]
+pub use check::check_balanced;
+pub use element::{Chunks, Element};
+pub use escape::{escape_attr, escape_html};
+#[doc(hidden)]
+pub use escape::{escape_cdata, escape_comment};
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+pub use io::IoWriter;
+#[cfg(feature = "axum")]
+pub use page::HiccupPage;
+pub use render::{Partial, PreEscaped, PrettyConfig, Render, Safe};
+#[doc(hidden)]
+pub use render::{Collection, CollectionKind, Components, HeadDefaults, IterKind, RenderKind, Renderer, TextKind};
+#[doc(hidden)]
+pub use strict::{is_block_tag, is_known_entity, is_known_role, is_known_tag};
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source", "track", "wbr",
+];
+
+// SVG elements that are usually empty, written self-closed like `<path d="M0 0"/>` when they have no children.
+const SVG_EMPTY_ELEMENTS: &[&str] = &[
+    "circle", "ellipse", "line", "path", "polygon", "polyline", "rect", "stop", "use",
+];
+
+/// Whether `tag` is an HTML void element, or an empty SVG shape, which are emitted self-closed, like `<br/>`.
+#[doc(hidden)]
+pub fn is_void_element(tag: &str) -> bool {
+    VOID_ELEMENTS.contains(&tag) || SVG_EMPTY_ELEMENTS.contains(&tag)
+}
+
+/// The rendering of `hiccup_each!`, `capacity` is for every item.
+#[doc(hidden)]
+pub fn render_each<I, F>(buf: &mut alloc::string::String, items: I, capacity: Option<usize>, mut render: F) -> core::fmt::Result
+where
+    I: IntoIterator,
+    I::IntoIter: ExactSizeIterator,
+    F: FnMut(&mut alloc::string::String, I::Item) -> core::fmt::Result,
+{
+    let mut items = items.into_iter();
+    if let Some(capacity) = capacity {
+        buf.reserve(capacity * items.len());
+    } else if let Some(first) = items.next() {
+        let start = buf.len();
+        render(buf, first)?;
+        buf.reserve((buf.len() - start) * items.len());
+    }
+    items.try_for_each(|item| render(buf, item))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Partial, PreEscaped, Render, Safe};
+
+    #[test]
+    fn basic_html() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out,
+            html[
+                head[title["Hiccup guide"]]
+                body[h1["Hiccup is the best!"]]
+            ]);
+
+            assert_eq!(out, "<html><head><title>Hiccup guide</title></head>\
+            <body><h1>Hiccup is the best!</h1></body></html>");
+    }
+
+    #[test]
+    fn attr_block() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out,
+            html[
+                head[title["Hiccup guide"]]
+                body[h1{class=>"value", c=>"v"}["Hiccup is the best!"]]
+            ]);
+
+        assert_eq!(out, "<html><head><title>Hiccup guide</title></head><body>\
+        <h1 class=\"value\" c=\"v\">Hiccup is the best!</h1></body></html>");
+    }
+
+    #[test]
+    fn empty_attribute_blocks() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, div{}["x"] div{id=>"a"}["x"] div{id=>"a", class=>"b"}["x"] br{} p.c{}["x"]);
+
+        assert_eq!(out, "<div>x</div><div id=\"a\">x</div><div id=\"a\" class=\"b\">x</div><br/><p class=\"c\">x</p>");
+    }
+
+    #[test]
+    fn attribute_separators() {
+        let cases = [
+            (hiccup_string!(img), "<img/>"),
+            (hiccup_string!(img{x=>"1"}), "<img x=\"1\"/>"),
+            (hiccup_string!(img{x=>"1", y=>"2"}), "<img x=\"1\" y=\"2\"/>"),
+            (hiccup_string!(img{x=>"1", y=>"2", z}), "<img x=\"1\" y=\"2\" z/>"),
+            (hiccup_string!(a), "<a></a>"),
+            (hiccup_string!(a{x=>"1"}), "<a x=\"1\"></a>"),
+            (hiccup_string!(a{x=>"1", y=>"2"}), "<a x=\"1\" y=\"2\"></a>"),
+            (hiccup_string!(a{x=>"1", y=>"2", z=>"3",}), "<a x=\"1\" y=\"2\" z=\"3\"></a>"),
+            (hiccup_string!(a["t"]), "<a>t</a>"),
+            (hiccup_string!(a{x=>"1"}["t"]), "<a x=\"1\">t</a>"),
+            (hiccup_string!(a{x=>"1", y=>"2"}["t"]), "<a x=\"1\" y=\"2\">t</a>"),
+            (hiccup_string!(a{x=>"1", y=>"2", z=>"3"}["t"]), "<a x=\"1\" y=\"2\" z=\"3\">t</a>"),
+            (hiccup_string!(x-a{}), "<x-a></x-a>"),
+            (hiccup_string!(x-a{x=>"1"}["t"]), "<x-a x=\"1\">t</x-a>"),
+            (hiccup_string!(x-a{x=>"1", y=>"2"}), "<x-a x=\"1\" y=\"2\"></x-a>"),
+            (hiccup_string!(x-a{x=>"1", y=>"2", z=>"3"}["t"]), "<x-a x=\"1\" y=\"2\" z=\"3\">t</x-a>"),
+        ];
+
+        for (html, expected) in cases.iter() {
+            assert_eq!(html, expected);
+        }
+    }
+
+    #[test]
+    fn attributes_keep_source_order() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, div{b=>"1", a=>"2"} div{z=>"1", checked, "aria-x"=>"2", m=>?(Some(3)), a=>"4"});
+
+        assert_eq!(out, "<div b=\"1\" a=\"2\"></div><div z=\"1\" checked aria-x=\"2\" m=\"3\" a=\"4\"></div>");
+    }
+
+    #[test]
+    fn text_is_escaped() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, p["<script>alert('x') & \"y\"</script>"]);
+
+        assert_eq!(out, "<p>&lt;script&gt;alert(&#39;x&#39;) &amp; &quot;y&quot;&lt;/script&gt;</p>");
+    }
+
+    #[test]
+    fn attr_value_is_escaped() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, a{href=>"x?a=1&b=2"}[]);
+
+        assert_eq!(out, "<a href=\"x?a=1&amp;b=2\"></a>");
+    }
+
+    #[test]
+    fn dynamic_attr_value() {
+        let mut out = String::new();
+        let url = String::from("https://github.com/naomijub/hiccup");
+
+        let _ = hiccup!(&mut out, a{href=>(url), class=>"link"}["repo"]);
+
+        assert_eq!(out, "<a href=\"https://github.com/naomijub/hiccup\" class=\"link\">repo</a>");
+    }
+
+    #[test]
+    fn dynamic_attr_value_from_fn_call() {
+        let mut out = String::new();
+        let user = |id: u32| format!("/users/{}?tab=repos&sort=asc", id);
+
+        let _ = hiccup!(&mut out, a{class=>"user", href=>(user(7))}["me"]);
+
+        assert_eq!(out, "<a class=\"user\" href=\"/users/7?tab=repos&amp;sort=asc\">me</a>");
+    }
+
+    #[test]
+    fn display_attr_values() {
+        struct Size(u32, u32);
+
+        impl std::fmt::Display for Size {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}x\"{}\" & up", self.0, self.1)
+            }
+        }
+
+        let mut out = String::new();
+        let i: usize = 3;
+        let ratio = 1.5f64;
+        let size = Size(16, 9);
+
+        let _ = hiccup_kebab!(&mut out, li{data_index=>(i), data_ratio=>(ratio), data_size=>(size)});
+
+        assert_eq!(out, "<li data-index=\"3\" data-ratio=\"1.5\" data-size=\"16x&quot;9&quot; &amp; up\"></li>");
+    }
+
+    #[test]
+    fn expression_children() {
+        let mut out = String::new();
+        let name = "Julia & co";
+        let count = 40 + 2;
+
+        let _ = hiccup!(&mut out,
+            div[
+                p[(count)]
+                p[(name)]
+                p[(format!("{} has {} stars", name, count))]
+            ]);
+
+        assert_eq!(out, "<div><p>42</p><p>Julia &amp; co</p><p>Julia &amp; co has 42 stars</p></div>");
+    }
+
+    #[test]
+    fn mixed_text_and_tags() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, p["a" b["b"] "c"]);
+
+        assert_eq!(out, "<p>a<b>b</b>c</p>");
+    }
+
+    #[test]
+    fn mixed_expressions_and_tags() {
+        let mut out = String::new();
+        let name = "world";
+
+        let _ = hiccup!(&mut out, p["Hello " b[(name)] (1 + 1) i["!"]]);
+
+        assert_eq!(out, "<p>Hello <b>world</b>2<i>!</i></p>");
+    }
+
+    #[test]
+    fn adjacent_text_is_concatenated() {
+        let mut out = String::new();
+        let name = "world";
+
+        let _ = hiccup!(&mut out, p["a" "b" "c"] p["Hello, "
+            (name)
+            "! A long text split "
+            "across lines."]);
+
+        assert_eq!(out, "<p>abc</p><p>Hello, world! A long text split across lines.</p>");
+    }
+
+    #[test]
+    fn void_elements_self_close() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, p["a" br "b"] img{src=>"x"} div[] script{src=>"app.js"});
+
+        assert_eq!(out, "<p>a<br/>b</p><img src=\"x\"/><div></div><script src=\"app.js\"></script>");
+    }
+
+    #[test]
+    fn custom_void_elements() {
+        let mut out = String::new();
+
+        let _ = hiccup_with_void!(&["tag1", "tag2"], &mut out, root[tag1[] tag2{a=>"b"} div[] img{src=>"x"} tag1["x"]]);
+
+        assert_eq!(out, "<root><tag1/><tag2 a=\"b\"/><div></div><img src=\"x\"></img><tag1>x</tag1></root>");
+    }
+
+    #[test]
+    fn template_renders_its_children() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, template[div["x"]] template{id=>"row"}[tr[td["<a & b>"]]] template);
+
+        assert!(!super::is_void_element("template"));
+        assert_eq!(out, "<template><div>x</div></template>\
+        <template id=\"row\"><tr><td>&lt;a &amp; b&gt;</td></tr></template><template></template>");
+    }
+
+    struct CappedWriter {
+        out: String,
+        cap: usize,
+    }
+
+    impl std::fmt::Write for CappedWriter {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            if self.out.len() + s.len() > self.cap {
+                return Err(std::fmt::Error);
+            }
+            self.out.push_str(s);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn any_writer() {
+        fn generic<W: std::fmt::Write + ?Sized>(w: &mut W) -> std::fmt::Result {
+            hiccup!(w, p{class=>"a"}[(1) "b"])
+        }
+        let expected = "<p class=\"a\">1b</p>";
+
+        let mut string = String::new();
+        let _ = hiccup!(&mut string, p{class=>"a"}[(1) "b"]);
+        assert_eq!(string, expected);
+
+        let mut string = String::new();
+        let dyn_writer: &mut dyn std::fmt::Write = &mut string;
+        let _ = hiccup!(dyn_writer, p{class=>"a"}[(1) "b"]);
+        let _ = generic(dyn_writer);
+        assert_eq!(string, expected.repeat(2));
+
+        let mut custom = CappedWriter { out: String::new(), cap: 100 };
+        let _ = hiccup!(&mut custom, p{class=>"a"}[(1) "b"]);
+        let _ = generic(&mut custom);
+        assert_eq!(custom.out, expected.repeat(2));
+    }
+
+    #[test]
+    fn write_errors_are_returned() {
+        let mut out = CappedWriter { out: String::new(), cap: 10 };
+
+        let result = hiccup!(&mut out, div[p["this does not fit"]]);
+
+        assert_eq!(result, Err(std::fmt::Error));
+        assert_eq!(out.out, "<div><p>");
+    }
+
+    #[test]
+    fn write_errors_propagate_with_question_mark() {
+        fn render(w: &mut CappedWriter) -> std::fmt::Result {
+            hiccup!(w, p["a"])?;
+            hiccup!(w, p["this does not fit"])?;
+            hiccup!(w, p["never written"])
+        }
+        let mut out = CappedWriter { out: String::new(), cap: 16 };
+
+        assert!(render(&mut out).is_err());
+        assert_eq!(out.out, "<p>a</p><p>");
+    }
+
+    #[test]
+    fn io_output_matches_string_output() {
+        let mut out = String::new();
+        let mut bytes: Vec<u8> = Vec::new();
+
+        let _ = hiccup!(&mut out, html[head[title["Hiccup"]] body{class=>"main"}[p["a & b"] br]]);
+        hiccup_io!(&mut bytes, html[head[title["Hiccup"]] body{class=>"main"}[p["a & b"] br]]).unwrap();
+
+        assert_eq!(String::from_utf8(bytes).unwrap(), out);
+    }
+
+    #[test]
+    fn io_errors_are_returned() {
+        let mut buf = [0u8; 4];
+        let mut out: &mut [u8] = &mut buf;
+
+        let err = hiccup_io!(&mut out, p["too long"]).unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+    }
+
+    #[derive(Default)]
+    struct FlushCounter {
+        written: Vec<u8>,
+        flushes: Vec<usize>,
+    }
+
+    impl std::io::Write for FlushCounter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.flushes.push(self.written.len());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn io_flushes_every_n_bytes() {
+        let mut out = FlushCounter::default();
+
+        hiccup_io_flushing!(&mut out, 10, ul[li["aaaa"] li["bbbb"] li["cccc"]]).unwrap();
+
+        assert_eq!(out.written, b"<ul><li>aaaa</li><li>bbbb</li><li>cccc</li></ul>");
+        assert_eq!(out.flushes, [12, 25, 38, 48]);
+
+        let mut out = FlushCounter::default();
+
+        hiccup_io!(&mut out, ul[li["aaaa"]]).unwrap();
+        hiccup_io_flushing!(&mut out, 100, br).unwrap();
+
+        assert_eq!(out.flushes, [27]);
+    }
+
+    #[test]
+    fn string_output_matches_writer_output() {
+        let mut out = String::new();
+        let name = "Julia";
+
+        let _ = hiccup!(&mut out, html[body{class=>"main"}[p["hi " b[(name)]] br]]);
+        let html = hiccup_string!(html[body{class=>"main"}[p["hi " b[(name)]] br]]);
+
+        assert_eq!(html, out);
+    }
+
+    #[test]
+    fn doctype() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, doctype html[body["hi"]]);
+
+        assert_eq!(out, "<!DOCTYPE html><html><body>hi</body></html>");
+    }
+
+    #[test]
+    fn comments() {
+        let mut out = String::new();
+        let version = "1.2.3";
+
+        let _ = hiccup!(&mut out, div[comment["build: " (version)] p["x"]]);
+
+        assert_eq!(out, "<div><!--build: 1.2.3--><p>x</p></div>");
+    }
+
+    #[test]
+    fn comment_double_hyphen_is_broken() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, comment["a -- b --> c---"]);
+
+        assert_eq!(out, "<!--a - - b - -> c- - - -->");
+    }
+
+    #[test]
+    fn fragments() {
+        let mut out = String::new();
+        let terms = [("a", "1"), ("b", "2")];
+
+        let _ = hiccup!(&mut out, frag[p["a"] p["b"]]);
+        assert_eq!(out, "<p>a</p><p>b</p>");
+
+        out.clear();
+        let _ = hiccup!(&mut out, dl[for ((t, d) in terms) [frag[dt[(t)] dd[(d)]]]] if (true) [frag["x" br]]);
+        assert_eq!(out, "<dl><dt>a</dt><dd>1</dd><dt>b</dt><dd>2</dd></dl>x<br/>");
+    }
+
+    #[test]
+    fn boolean_attributes() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, input{type=>"checkbox", checked} input{required, name=>"q", autofocus});
+
+        assert_eq!(out, "<input type=\"checkbox\" checked/><input required name=\"q\" autofocus/>");
+    }
+
+    #[test]
+    fn boolean_or_valued_attributes() {
+        let mut out = String::new();
+        let name: Option<&str> = None;
+
+        let _ = hiccup!(&mut out,
+            a{href=>"/a.txt", download}["a"]
+            a{href=>"/b", download=>"file.txt"}["b"]
+            a{href=>"/c", download=>?name}["c"]);
+
+        assert_eq!(out, "<a href=\"/a.txt\" download>a</a><a href=\"/b\" download=\"file.txt\">b</a><a href=\"/c\">c</a>");
+    }
+
+    #[test]
+    fn microdata() {
+        let mut out = String::new();
+        let name = "Tom & Jerry";
+
+        let _ = hiccup!(&mut out,
+            div{itemscope, itemtype=>"https://schema.org/Person?a=1&b=2"}[
+                span{itemprop=>"name"}[(name)]
+                a{itemprop=>"url", href=>"/tom"}["home"]
+                div{itemprop=>"address", itemscope, itemtype=>"https://schema.org/PostalAddress"}[]
+            ]);
+
+        assert_eq!(out, "<div itemscope itemtype=\"https://schema.org/Person?a=1&amp;b=2\">\
+        <span itemprop=\"name\">Tom &amp; Jerry</span><a itemprop=\"url\" href=\"/tom\">home</a>\
+        <div itemprop=\"address\" itemscope itemtype=\"https://schema.org/PostalAddress\"></div></div>");
+    }
+
+    #[test]
+    fn conditional_boolean_attributes() {
+        let mut out = String::new();
+
+        for is_disabled in [true, false] {
+            let _ = hiccup!(&mut out,
+                input{disabled if is_disabled}
+                input{type=>"checkbox", checked if !is_disabled, id=>"c"});
+        }
+
+        assert_eq!(out, "<input disabled/><input type=\"checkbox\" id=\"c\"/><input/><input type=\"checkbox\" checked id=\"c\"/>");
+    }
+
+    #[test]
+    fn string_attribute_keys() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, button{"data-foo"=>"bar", class=>"x", "aria-label"=>"close"}["X"]);
+
+        assert_eq!(out, "<button data-foo=\"bar\" class=\"x\" aria-label=\"close\">X</button>");
+    }
+
+    #[test]
+    fn namespaced_attribute_keys() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out,
+            svg{xmlns=>"http://www.w3.org/2000/svg", xmlns:xlink=>"http://www.w3.org/1999/xlink"}[
+                use{xlink:href=>"#icon"}
+                text{"xml:lang"=>"en", xml:space=>"preserve"}["hi"]
+            ]);
+
+        assert_eq!(out, "<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\">\
+        <use xlink:href=\"#icon\"/><text xml:lang=\"en\" xml:space=\"preserve\">hi</text></svg>");
+    }
+
+    #[test]
+    fn keyword_attribute_keys() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out,
+            label{for=>"name"}["Name"] input{type=>"text", as=>"x", loop, async, "type"=>"y", "for"=>"z"});
+
+        assert_eq!(out, "<label for=\"name\">Name</label>\
+        <input type=\"text\" as=\"x\" loop async type=\"y\" for=\"z\"/>");
+    }
+
+    #[test]
+    fn conditional_rendering() {
+        let mut out = String::new();
+        let logged = true;
+        let admin = false;
+
+        let _ = hiccup!(&mut out,
+            nav[
+                if (logged) [a{href=>"/logout"}["logout"]]
+                if (admin) [a{href=>"/admin"}["admin"]]
+                if (admin) [p["admin"]] else [p["user"]]
+            ]);
+
+        assert_eq!(out, "<nav><a href=\"/logout\">logout</a><p>user</p></nav>");
+    }
+
+    #[test]
+    fn match_rendering() {
+        enum Status {
+            Active,
+            Pending(u32),
+            Failed { code: u16 },
+        }
+        let mut out = String::new();
+
+        for status in [Status::Active, Status::Pending(1), Status::Pending(3), Status::Failed { code: 500 }] {
+            let _ = hiccup!(&mut out, match (status) {
+                Status::Active => [span.badge.green["active"]],
+                Status::Pending(n) if n > 1 => [span.badge["pending " (n) " jobs"]],
+                Status::Pending(_) => [span.badge["pending"]],
+                Status::Failed { code: 500 } | Status::Failed { code: 502 } => [strong["server error"]],
+                Status::Failed { code } => [strong["failed " (code)]],
+            } br);
+        }
+
+        assert_eq!(out, "<span class=\"badge green\">active</span><br/><span class=\"badge\">pending</span><br/>\
+        <span class=\"badge\">pending 3 jobs</span><br/><strong>server error</strong><br/>");
+    }
+
+    #[test]
+    fn let_bindings() {
+        let mut out = String::new();
+        let users = [("Ana", 3), ("Bia", 1)];
+
+        let _ = hiccup!(&mut out, ul[for ((name, n) in users) [
+            let label = (format!("{} ({})", name, n));
+            li[(label)]
+            li{title=>(label)}
+        ]]);
+
+        assert_eq!(out, "<ul><li>Ana (3)</li><li title=\"Ana (3)\"></li><li>Bia (1)</li><li title=\"Bia (1)\"></li></ul>");
+    }
+
+    #[test]
+    fn iteration() {
+        let mut out = String::new();
+        let items = ["one", "two", "three"];
+
+        let _ = hiccup!(&mut out, ul[for (item in &items) [li[(item)]]]);
+
+        assert_eq!(out, "<ul><li>one</li><li>two</li><li>three</li></ul>");
+    }
+
+    #[test]
+    fn iteration_over_into_iterator() {
+        let mut out = String::new();
+        let links = vec![("/a", "A"), ("/b", "B")];
+
+        let _ = hiccup!(&mut out, nav[for ((href, text) in links.into_iter().rev()) [a{href=>(href)}[(text)]]]);
+
+        assert_eq!(out, "<nav><a href=\"/b\">B</a><a href=\"/a\">A</a></nav>");
+    }
+
+    #[test]
+    fn nested_iteration() {
+        let mut out = String::new();
+        let rows = [["a", "b"], ["c", "d"]];
+
+        let _ = hiccup!(&mut out, table[for ((i, row) in rows.iter().enumerate()) [
+            tr{data_row=>(i)}[for (cell in row) [td[(i) (cell)]]]
+        ]]);
+
+        assert_eq!(out, "<table><tr data_row=\"0\"><td>0a</td><td>0b</td></tr>\
+        <tr data_row=\"1\"><td>1c</td><td>1d</td></tr></table>");
+    }
+
+    #[test]
+    fn optional_text() {
+        let render = |label: Option<&str>| {
+            let mut out = String::new();
+            let _ = hiccup!(&mut out, span[?label] p["a" ?(label.map(|l| l.len())) b["b"]]);
+            out
+        };
+
+        assert_eq!(render(Some("x")), "<span>x</span><p>a1<b>b</b></p>");
+        assert_eq!(render(None), "<span></span><p>a<b>b</b></p>");
+        assert_eq!(render(Some("<x>")), "<span>&lt;x&gt;</span><p>a3<b>b</b></p>");
+    }
+
+    #[test]
+    fn formatted_text() {
+        let mut out = String::new();
+        let name = "<Tom & Jerry>";
+        let count = 2;
+
+        let _ = hiccup!(&mut out, p[text!("Hello {}, {} new", name, count) b[text!("{count}")]] svg[text["t"]]);
+
+        assert_eq!(out, "<p>Hello &lt;Tom &amp; Jerry&gt;, 2 new<b>2</b></p><svg><text>t</text></svg>");
+    }
+
+    #[test]
+    fn raw_is_not_escaped() {
+        let mut out = String::new();
+        let markdown = String::from("<em>rendered</em>");
+
+        let _ = hiccup!(&mut out, div[raw[("<b>x</b>") (markdown)] p["<b>x</b>"]]);
+
+        assert_eq!(out, "<div><b>x</b><em>rendered</em><p>&lt;b&gt;x&lt;/b&gt;</p></div>");
+    }
+
+    #[test]
+    fn pretty_print() {
+        let mut out = String::new();
+
+        let _ = hiccup_pretty!(&mut out,
+            doctype html[
+                head[meta{charset=>"utf-8"} title["Hiccup guide"]]
+                body[div{class=>"main"}[p["Hello " b["world"]] br]]
+            ]);
+
+        assert_eq!(out, "<!DOCTYPE html>
+<html>
+  <head>
+    <meta charset=\"utf-8\"/>
+    <title>Hiccup guide</title>
+  </head>
+  <body>
+    <div class=\"main\">
+      <p>Hello <b>world</b></p><br/>
+    </div>
+  </body>
+</html>");
+    }
+
+    #[test]
+    fn pre_escaped_is_not_escaped() {
+        let mut out = String::new();
+        let fragment = String::from("<b>bold</b>");
+
+        let _ = hiccup!(&mut out, p[(PreEscaped("<i>ok</i>")) (PreEscaped(&fragment)) ("<i>no</i>")]);
+
+        assert_eq!(out, "<p><i>ok</i><b>bold</b>&lt;i&gt;no&lt;/i&gt;</p>");
+    }
+
+    struct Card {
+        title: String,
+        body: &'static str,
+    }
+
+    impl Render for Card {
+        fn render(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+            hiccup!(w, div{class=>"card"}[h2[(self.title)] p[(self.body)]])
+        }
+    }
+
+    #[test]
+    fn render_components() {
+        let mut out = String::new();
+        let card = Card { title: String::from("Hiccup & Rust"), body: "components" };
+
+        let _ = hiccup!(&mut out, div{class=>"cards"}[(card) (&card)]);
+
+        assert_eq!(out, "<div class=\"cards\">\
+        <div class=\"card\"><h2>Hiccup &amp; Rust</h2><p>components</p></div>\
+        <div class=\"card\"><h2>Hiccup &amp; Rust</h2><p>components</p></div></div>");
+    }
+
+    struct Li<'a>(&'a str);
+
+    impl Render for Li<'_> {
+        fn render(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+            hiccup!(w, li[(self.0)])
+        }
+    }
+
+    #[test]
+    fn render_iterator_of_components() {
+        let mut out = String::new();
+        let items = ["a", "b & c", "d"];
+
+        let _ = hiccup!(&mut out, ul[(items.iter().map(|i| Li(i)))] ol[(items.iter().take(0).map(|i| Li(i)))]);
+
+        assert_eq!(out, "<ul><li>a</li><li>b &amp; c</li><li>d</li></ul><ol></ol>");
+    }
+
+    struct Row(u32);
+
+    impl Render for Row {
+        fn render(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+            hiccup!(w, tr[td[(self.0)]])
+        }
+    }
+
+    #[test]
+    fn render_collection_of_components() {
+        let mut out = String::new();
+        let rows = vec![Row(1), Row(2)];
+        let array = [Row(3)];
+
+        let _ = hiccup!(&mut out, table[(rows) (array) (rows[..1])]);
+
+        assert_eq!(out, "<table><tr><td>1</td></tr><tr><td>2</td></tr><tr><td>3</td></tr><tr><td>1</td></tr></table>");
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[test]
+    fn class_shorthand() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, div.card.big["x"] span.label br.clear hr);
+
+        assert_eq!(out, "<div class=\"card big\">x</div><span class=\"label\"></span><br class=\"clear\"/><hr/>");
+    }
+
+    #[test]
+    fn class_shorthand_with_attributes() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, div.card{id=>"a", class=>"big"}["x"] a.btn{href=>"/"}["home"] p.note{class=>""}[] div.c{class}[]);
+
+        assert_eq!(out, "<div id=\"a\" class=\"card big\">x</div><a href=\"/\" class=\"btn\">home</a>\
+        <p class=\"note\"></p><div class=\"c\"></div>");
+    }
+
+    #[test]
+    fn id_shorthand() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, div#main["x"] div#main.card["x"] div.card#main.big{role=>"main"}["x"]);
+
+        assert_eq!(out, "<div id=\"main\">x</div><div id=\"main\" class=\"card\">x</div>\
+        <div id=\"main\" role=\"main\" class=\"card big\">x</div>");
+    }
+
+    #[test]
+    fn optional_attributes() {
+        let mut out = String::new();
+        let url: Option<&str> = Some("/home");
+        let title: Option<String> = None;
+
+        let _ = hiccup!(&mut out, a{href=>?url, title=>?title}["home"] a{title=>?title}["x"] a{"data-id"=>?(Some(1))}[]);
+
+        assert_eq!(out, "<a href=\"/home\">home</a><a>x</a><a data-id=\"1\"></a>");
+    }
+
+    #[test]
+    fn style_maps() {
+        let mut out = String::new();
+        let weight = "bold";
+
+        let _ = hiccup!(&mut out,
+            div{id=>"a", style=>{color=>"red", font_weight=>(weight), "--gap"=>2,}, hidden}
+            p{style=>{background_image=>"url(\"a.png\")"}}["x"]);
+
+        assert_eq!(out, "<div id=\"a\" style=\"color:red;font-weight:bold;--gap:2\" hidden></div>\
+        <p style=\"background-image:url(&quot;a.png&quot;)\">x</p>");
+    }
+
+    #[test]
+    fn kebab_keys() {
+        let mut out = String::new();
+
+        let _ = hiccup_kebab!(&mut out,
+            head[
+                meta{http_equiv=>"refresh", content=>"30"}
+                form{accept_charset=>"utf-8", data_user_id=>1}[input{aria_hidden=>"true", no_validate}]
+            ]);
+        assert_eq!(out, "<head><meta http-equiv=\"refresh\" content=\"30\"/>\
+        <form accept-charset=\"utf-8\" data-user-id=\"1\"><input aria-hidden=\"true\" no-validate/></form></head>");
+
+        out.clear();
+        let _ = hiccup!(&mut out, meta{http_equiv=>"refresh"});
+        assert_eq!(out, "<meta http_equiv=\"refresh\"/>");
+    }
+
+    #[test]
+    fn list_attributes() {
+        let mut out = String::new();
+        let classes: &[&str] = &["a", "", "b"];
+        let empty: Vec<String> = Vec::new();
+
+        let _ = hiccup!(&mut out, div{class=>@classes}["x"] div.card{class=>@(["big", ""])}[] p{class=>@empty}[]);
+
+        assert_eq!(out, "<div class=\"a b\">x</div><div class=\"card big\"></div><p></p>");
+    }
+
+    #[test]
+    fn splatted_attributes() {
+        let mut out = String::new();
+        let attrs = vec![("data-id".to_string(), "7".to_string()), ("title".to_string(), "\"a\" & b".to_string())];
+        let sorted: std::collections::BTreeMap<&str, i32> = vec![("z", 1), ("a", 2)].into_iter().collect();
+
+        let _ = hiccup!(&mut out, div.card{id=>"x", ..attrs}["x"] p{..sorted, class=>"big", ..Vec::<(&str, &str)>::new()}[]);
+
+        assert_eq!(out, "<div id=\"x\" data-id=\"7\" title=\"&quot;a&quot; &amp; b\" class=\"card\">x</div>\
+        <p a=\"2\" z=\"1\" class=\"big\"></p>");
+
+        for key in ["onclick=\"alert(1)\" x", "a b", "a>", "", "a/", "a\u{0}"] {
+            let mut out = String::new();
+            assert_eq!(hiccup!(&mut out, div{..vec![(key, "y")]}[]), Err(std::fmt::Error), "{:?}", key);
+            assert!(!out.contains("alert"));
+        }
+    }
+
+    #[test]
+    fn conditional_classes() {
+        let render = |is_active: bool, size: &str| {
+            let mut out = String::new();
+            let _ = hiccup!(&mut out, div.box{class=>["active" if is_active, "card", (size) if !size.is_empty(),]}[]);
+            out
+        };
+
+        assert_eq!(render(true, "big"), "<div class=\"box active card big\"></div>");
+        assert_eq!(render(false, "big"), "<div class=\"box card big\"></div>");
+        assert_eq!(render(false, ""), "<div class=\"box card\"></div>");
+
+        let mut out = String::new();
+        let _ = hiccup!(&mut out, p{class=>["a" if false, "b" if 1 > 2]}["x"]);
+        assert_eq!(out, "<p>x</p>");
+    }
+
+    #[test]
+    fn inline_svg() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out,
+            svg{viewBox=>"0 0 10 10", width=>"10"}[
+                g{fill=>"none"}[
+                    path{d=>"M0 0 L1 1, L2 2 Z"}
+                    circle{cx=>"5", cy=>"5", r=>"4"}
+                ]
+                text{x=>"1"}["a < b"]
+            ]);
+
+        assert_eq!(out, "<svg viewBox=\"0 0 10 10\" width=\"10\"><g fill=\"none\">\
+        <path d=\"M0 0 L1 1, L2 2 Z\"/><circle cx=\"5\" cy=\"5\" r=\"4\"/></g>\
+        <text x=\"1\">a &lt; b</text></svg>");
+    }
+
+    #[test]
+    fn with_capacity() {
+        let items = ["a", "b", "c"];
+
+        let html = hiccup_with_capacity!(256, ul[for (i in &items) [li[(i)]]]);
+
+        assert_eq!(html, hiccup_string!(ul[for (i in &items) [li[(i)]]]));
+        assert_eq!(html.capacity(), 256);
+    }
+
+    #[test]
+    fn literal_attribute_values_are_quoted() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, input{maxlength=>10, hidden=>true, name=>"x", step=>0.5});
+
+        assert_eq!(out, "<input maxlength=\"10\" hidden=\"true\" name=\"x\" step=\"0.5\"/>");
+    }
+
+    #[test]
+    fn minified_text() {
+        let mut out = String::new();
+        let name = "Julia \t Naomi";
+
+        let _ = hiccup_min!(&mut out,
+            div[
+                p["Hello,
+                   world  " b[(name)]]
+                pre["  fn main() {\n      hiccup!();\n  }"]
+                textarea{name=>"t"}["a\n\n  b"]
+            ]);
+
+        assert_eq!(out, "<div><p>Hello, world <b>Julia Naomi</b></p>\
+        <pre>  fn main() {\n      hiccup!();\n  }</pre><textarea name=\"t\">a\n\n  b</textarea></div>");
+
+        let mut out = String::new();
+
+        let _ = hiccup_min!(&mut out, p["a " " b" (" \n c")] p["d " b[" e"]]);
+
+        assert_eq!(out, "<p>a b c</p><p>d <b> e</b></p>");
+    }
+
+    #[test]
+    fn pre_whitespace_is_preserved() {
+        let mut out = String::new();
+        let _ = hiccup_pretty!(&mut out, div[pre["  line1\n\tline2  "] pre[b["x"] div["y"]]]);
+        assert_eq!(out, "<div>\n  <pre>  line1\n\tline2  </pre>\n  <pre><b>x</b><div>y</div></pre>\n</div>");
+
+        let mut out = String::new();
+        let _ = hiccup_min!(&mut out, pre["  line1\n\n  line2"]);
+        assert_eq!(out, "<pre>  line1\n\n  line2</pre>");
+
+        let mut out = String::new();
+        let _ = hiccup!(&mut out, pre["\nfirst"] textarea["\n"] pre[b["x"] "\ny"]);
+        assert_eq!(out, "<pre>\n\nfirst</pre><textarea>\n\n</textarea><pre><b>x</b>\ny</pre>");
+    }
+
+    #[test]
+    fn custom_elements() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out,
+            my-widget["x"]
+            ion-button{color=>"primary"}["ok"]
+            x-multi-part-name.big#main
+            my-icon);
+
+        assert_eq!(out, "<my-widget>x</my-widget><ion-button color=\"primary\">ok</ion-button>\
+        <x-multi-part-name id=\"main\" class=\"big\"></x-multi-part-name><my-icon></my-icon>");
+    }
+
+    #[test]
+    fn xml_rss_item() {
+        let mut out = String::new();
+        let link = "https://example.com/?a=1&b=2";
+
+        let _ = hiccup_xml!(&mut out,
+            xml rss{version=>"2.0"}[channel[item[
+                title["Tom & Jerry"]
+                link[(link)]
+                guid{isPermaLink=>false}[(link)]
+                category[]
+                enclosure{url=>"a.mp3", length=>10}
+                br
+            ]]]);
+
+        assert_eq!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?><rss version=\"2.0\"><channel><item>\
+        <title>Tom &amp; Jerry</title><link>https://example.com/?a=1&amp;b=2</link>\
+        <guid isPermaLink=\"false\">https://example.com/?a=1&amp;b=2</guid>\
+        <category/><enclosure url=\"a.mp3\" length=\"10\"/><br/></item></channel></rss>");
+    }
+
+    #[test]
+    fn xml_root_element() {
+        let mut out = String::new();
+
+        let _ = hiccup_xml!(&mut out, xml{version=>"1"}["x"]);
+        assert_eq!(out, "<xml version=\"1\">x</xml>");
+
+        out.clear();
+        let _ = hiccup_xml!(&mut out, xml xml["x"]);
+        assert_eq!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?><xml>x</xml>");
+
+        out.clear();
+        let _ = hiccup_xml!(&mut out, { let n = 1; } xml n[(n)]);
+        assert_eq!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?><n>1</n>");
+
+        out.clear();
+        let _ = hiccup!(&mut out, xml["x"]);
+        assert_eq!(out, "<xml>x</xml>");
+    }
+
+    #[test]
+    fn meta_charset() {
+        let mut out = String::new();
+
+        let _ = hiccup_html5!(&mut out, head[meta_charset meta_charset["iso-8859-1"] meta{charset=>"utf-8"} br]);
+        assert_eq!(out.as_bytes(), b"<head><meta charset=\"utf-8\"><meta charset=\"iso-8859-1\"><meta charset=\"utf-8\"><br></head>");
+
+        out.clear();
+        let _ = hiccup!(&mut out, head[meta_charset div]);
+        assert_eq!(out, "<head><meta charset=\"utf-8\"/><div></div></head>");
+    }
+
+    #[test]
+    fn attributes_after_children_are_merged() {
+        let mut out = String::new();
+        let active = "active";
+
+        let _ = hiccup!(&mut out,
+            div["x"]{class=>"y"}
+            a{href=>"/",}["home"]{class=>(active)}
+            p{}["a"]{id=>"b"}{hidden}
+            div.card["z"]{title=>"t"}
+            x-tab["w"]{selected});
+
+        assert_eq!(out, "<div class=\"y\">x</div><a href=\"/\" class=\"active\">home</a>\
+        <p id=\"b\" hidden>a</p><div title=\"t\" class=\"card\">z</div><x-tab selected>w</x-tab>");
+
+        out.clear();
+        let _ = hiccup_strict!(&mut out, div["x"]{class=>"y"}{id=>"z"});
+        assert_eq!(out, "<div class=\"y\" id=\"z\">x</div>");
+    }
+
+    #[test]
+    fn conditional_comments() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, head[cond_comment["lt IE 9"][link{rel=>"stylesheet", href=>"ie.css"}] title["x"]]);
+
+        assert_eq!(out, "<head><!--[if lt IE 9]><link rel=\"stylesheet\" href=\"ie.css\"/><![endif]--><title>x</title></head>");
+    }
+
+    #[test]
+    fn cdata_sections() {
+        let mut out = String::new();
+        let script = "if (a < b && c) {}";
+
+        let _ = hiccup_xml!(&mut out, item[description[cdata["some & <raw> " (script)]] data[cdata["a]]>b"]]]);
+
+        assert_eq!(out, "<item><description><![CDATA[some & <raw> if (a < b && c) {}]]></description>\
+        <data><![CDATA[a]]]]><![CDATA[>b]]></data></item>");
+    }
+
+    #[test]
+    fn strict_phrasing_in_p() {
+        let mut out = String::new();
+        let items = ["a", "b"];
+        let section: Option<&str> = Some(" s");
+        let main: Option<&str> = None;
+
+        let _ = hiccup_strict!(&mut out,
+            div[
+                p.intro{title=>"x"}[
+                    "a " b["b"] a{href=>"/"}[span.x["c"]] br
+                    if (true) [i["d"]] else [em["e"]]
+                    for (i in &items) [code[(i)]]
+                    x-badge["f"] frag[small["g"]] ?(Some(1)) ?section ?main
+                ]
+                div[p["h"]]
+            ]);
+
+        assert_eq!(out, "<div><p title=\"x\" class=\"intro\">a <b>b</b><a href=\"/\"><span class=\"x\">c</span></a><br/>\
+        <i>d</i><code>a</code><code>b</code><x-badge>f</x-badge><small>g</small>1 s</p><div><p>h</p></div></div>");
+    }
+
+    #[test]
+    fn document_with_lang() {
+        let lang = String::from("pt-BR");
+
+        let html = hiccup_doc!(lang, body{dir=>"ltr"}[p["Olá"]]);
+        assert!(html.starts_with("<!DOCTYPE html><html lang=\"pt-BR\"><body dir=\"ltr\">"));
+        assert_eq!(html, "<!DOCTYPE html><html lang=\"pt-BR\"><body dir=\"ltr\"><p>Olá</p></body></html>");
+
+        assert_eq!(hiccup_doc!("en", ), "<!DOCTYPE html><html lang=\"en\"></html>");
+    }
+
+    #[test]
+    fn csrf_input() {
+        let mut out = String::new();
+        let token = String::from("a&b\"c");
+
+        let _ = hiccup!(&mut out, form{action=>"/x", method=>"post"}[csrf(token) button["Send"]]);
+
+        assert_eq!(out, "<form action=\"/x\" method=\"post\">\
+        <input type=\"hidden\" name=\"_csrf\" value=\"a&amp;b&quot;c\"/><button>Send</button></form>");
+    }
+
+    #[test]
+    fn render_each_item() {
+        let items = ["a", "b & c", "d"];
+
+        let mut buf = String::new();
+        let _ = hiccup_each!(&mut buf, &items, |buf, item| hiccup!(buf, li[(item)]));
+        assert_eq!(buf, "<li>a</li><li>b &amp; c</li><li>d</li>");
+
+        let mut buf = String::new();
+        let result = hiccup_each!(&mut buf, items.iter().enumerate(), 64, |buf, (i, item)| {
+            hiccup!(buf, div{id=>(i)}[(item)])
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(buf.capacity(), 3 * 64);
+        assert_eq!(buf, "<div id=\"0\">a</div><div id=\"1\">b &amp; c</div><div id=\"2\">d</div>");
+
+        let mut buf = String::new();
+        let result = hiccup_each!(&mut buf, &items, |_: &mut String, _| Err(std::fmt::Error));
+        assert_eq!(result, Err(std::fmt::Error));
+        assert_eq!(hiccup_each!(&mut buf, Vec::<&str>::new(), |buf, item| hiccup!(buf, li[(item)])), Ok(()));
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn head_defaults() {
+        let mut out = String::new();
+        let title = "Tom & Jerry";
+
+        let _ = hiccup!(&mut out, (head_defaults!(title)));
+
+        assert_eq!(out, "<head><meta charset=\"utf-8\">\
+        <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+        <title>Tom &amp; Jerry</title></head>");
+    }
+
+    #[test]
+    fn head_defaults_is_html_only() {
+        let mut out = String::new();
+
+        let _ = hiccup_xml!(&mut out, html[(head_defaults!("x")) body[br]]);
+
+        assert_eq!(out, "<html><head><meta charset=\"utf-8\">\
+        <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+        <title>x</title></head><body><br/></body></html>");
+    }
+
+    #[test]
+    fn lowercase_tags() {
+        let mut out = String::new();
+
+        let _ = hiccup_lower!(&mut out, Div["x"] TABLE[Tr[td["y"]]] IMG X-Card.Big["z"]);
+        assert_eq!(out, "<div>x</div><table><tr><td>y</td></tr></table><img/><x-card class=\"Big\">z</x-card>");
+
+        out.clear();
+        let _ = hiccup!(&mut out, Div["x"]);
+        assert_eq!(out, "<Div>x</Div>");
+    }
+
+    #[test]
+    fn ascii_text() {
+        let mut out = String::new();
+        let name = "José";
+
+        let _ = hiccup_ascii!(&mut out, p{title=>"é"}["Olá, " (name) " 🎉 & <b>"]);
+
+        assert_eq!(out, "<p title=\"é\">Ol&#225;, Jos&#233; &#127881; &amp; &lt;b&gt;</p>");
+    }
+
+    #[test]
+    fn single_quoted_attributes() {
+        let mut out = String::new();
+        let svg = "data:image/svg+xml,<svg xmlns=\"http://www.w3.org/2000/svg\"/>";
+
+        let _ = hiccup_squote!(&mut out,
+            div#main.card{class=>"big", title=>"Tom's \"cat\" & co", style=>{color=>"red"}}[
+                img{src=>(svg), alt=>"it's"}
+                input{hidden}
+            ]);
+
+        assert_eq!(out, "<div id='main' class='card big' title='Tom&#39;s \"cat\" &amp; co' style='color:red'>\
+        <img src='data:image/svg+xml,&lt;svg xmlns=\"http://www.w3.org/2000/svg\"/&gt;' alt='it&#39;s'/>\
+        <input hidden/></div>");
+    }
+
+    #[test]
+    fn safe_urls() {
+        let mut out = String::new();
+        let attack = "javascript:alert(1)";
+
+        let _ = hiccup_safe_urls!(&mut out, div[
+            a{href=>(attack)}["a"]
+            a{href=>"  JAVA\tSCRIPT:alert(1)"}["b"]
+            img{src=>"vbscript:msgbox(1)"}
+            a{href=>"https://example.com/?q=javascript:"}["c"]
+            a{title=>(attack)}["d"]
+            a{HREF=>(attack)}["e"]
+            a{..vec![("HREF", attack)]}["f"]
+            svg[a{xlink:href=>(attack)}[]]
+            form{action=>(attack)}[button{formAction=>(attack)}["g"]]
+        ]);
+
+        assert_eq!(out, "<div><a href=\"#\">a</a><a href=\"#\">b</a><img src=\"#\"/>\
+        <a href=\"https://example.com/?q=javascript:\">c</a><a title=\"javascript:alert(1)\">d</a>\
+        <a HREF=\"#\">e</a><a HREF=\"#\">f</a><svg><a xlink:href=\"#\"></a></svg>\
+        <form action=\"#\"><button formAction=\"#\">g</button></form></div>");
+
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, a{href=>(attack)}["a"]);
+
+        assert_eq!(out, "<a href=\"javascript:alert(1)\">a</a>");
+    }
+
+    struct Link(&'static str);
+
+    impl Render for Link {
+        fn render(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+            hiccup!(w, a{href=>(self.0)}["link"])
+        }
+    }
+
+    struct SafeLink(&'static str);
+
+    impl Render for SafeLink {
+        fn render(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+            hiccup_safe_urls!(w, a{href=>(self.0)}["link"])
+        }
+    }
+
+    // The modes of a macro don't reach the components, which render with their own macro.
+    #[test]
+    fn modes_stop_at_components() {
+        let mut out = String::new();
+
+        let _ = hiccup_safe_urls!(&mut out, div[(Link("javascript:alert(1)")) (SafeLink("javascript:alert(1)"))]);
+
+        assert_eq!(out, "<div><a href=\"javascript:alert(1)\">link</a><a href=\"#\">link</a></div>");
+
+        let mut out = String::new();
+
+        let _ = hiccup_pretty!(&mut out, main[(Link("/")) section[p["x"]]]);
+
+        assert_eq!(out, "<main><a href=\"/\">link</a>\n  <section>\n    <p>x</p>\n  </section>\n</main>");
+    }
+
+    #[test]
+    fn append_lines() {
+        let mut doc = String::from("<!-- report -->\n");
+
+        hiccup_writeln!(&mut doc, div["x"]).unwrap();
+        hiccup_writeln!(&mut doc, div.y[(1 + 1)]).unwrap();
+
+        assert_eq!(doc, "<!-- report -->\n<div>x</div>\n<div class=\"y\">2</div>\n");
+    }
+
+    #[test]
+    fn checked_balanced_output() {
+        let mut out = String::new();
+
+        hiccup_checked!(&mut out, ul[li[raw["<b>a</b>"]] li{hidden}["b"]] raw["<hr>"]).unwrap();
+
+        assert_eq!(out, "<ul><li><b>a</b></li><li hidden>b</li></ul><hr>");
+    }
+
+    #[test]
+    #[should_panic(expected = "hiccup: unbalanced output, `</section>` closes `<div>`: <section><div></section>")]
+    fn checked_unbalanced_raw() {
+        let mut out = String::new();
+
+        let _ = hiccup_checked!(&mut out, section[raw["<div>"]]);
+    }
+
+    #[test]
+    fn leading_statements() {
+        let mut out = String::new();
+        let words = "b a c";
+
+        let _ = hiccup!(&mut out, {
+            let mut sorted: Vec<&str> = words.split(' ').collect();
+            sorted.sort();
+            let count = sorted.len();
+        } ul{data_count=>(count)}[for (word in &sorted) [li[(word)]]]);
+
+        assert_eq!(out, "<ul data_count=\"3\"><li>a</li><li>b</li><li>c</li></ul>");
+
+        let mut out = String::new();
+
+        let _ = hiccup_strict!(&mut out, { let title = "t"; } doctype html[head[title[(title)]]]);
+
+        assert_eq!(out, "<!DOCTYPE html><html><head><title>t</title></head></html>");
+    }
+
+    #[test]
+    fn strict_aria_roles() {
+        let mut out = String::new();
+        let role = "anything";
+
+        let _ = hiccup_strict!(&mut out,
+            div{role=>"button", "aria-pressed"=>"false"}["ok"]
+            p{"role"=>"switch button"}[span{role=>(role)}[]]
+            ul[li["x"]]{role=>"list"}
+            svg[a{xlink:role=>"http://x", role=>"link"}[]]);
+
+        assert_eq!(out, "<div role=\"button\" aria-pressed=\"false\">ok</div>\
+        <p role=\"switch button\"><span role=\"anything\"></span></p><ul role=\"list\"><li>x</li></ul>\
+        <svg><a xlink:role=\"http://x\" role=\"link\"></a></svg>");
+        assert!(crate::is_known_role("none"));
+        assert!(!crate::is_known_role("buton"));
+        assert!(!crate::is_known_role(" "));
+    }
+
+    #[test]
+    fn safe_fragments() {
+        let mut out = String::new();
+        let fragment = String::from("<b class=\"x\">Tom &amp; Jerry</b>");
+        let safe = Safe::from(String::from("<hr/>"));
+
+        let _ = hiccup!(&mut out, div[(Safe(fragment.clone())) (&safe) "<b>"]);
+
+        assert_eq!(out, "<div><b class=\"x\">Tom &amp; Jerry</b><hr/>&lt;b&gt;</div>");
+    }
+
+    #[test]
+    fn entities() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, p["a" entity(nbsp) "b" entity(mdash) b["c"] entity(copy)]);
+
+        assert_eq!(out, "<p>a&nbsp;b&mdash;<b>c</b>&copy;</p>");
+    }
+
+    #[test]
+    fn script_and_style_text_is_not_escaped() {
+        let mut out = String::new();
+        let data = "\"</SCRIPT><b>\"";
+
+        let _ = hiccup!(&mut out,
+            script["if (a < b && c > d) { x = '</script>'; }"]
+            script["var s = " (data) ";"]
+            style["a > b { content: \"</style>\"; }"]
+            p["a < b"]);
+
+        assert_eq!(out, "<script>if (a < b && c > d) { x = '<\\/script>'; }</script>\
+        <script>var s = \"<\\/SCRIPT><b>\";</script><style>a > b { content: \"<\\/style>\"; }</style><p>a &lt; b</p>");
+
+        out.clear();
+        let _ = hiccup_min!(&mut out, script["a\n  b </div>"]);
+        assert_eq!(out, "<script>a\n  b </div></script>");
+    }
+
+    #[test]
+    fn template_functions() {
+        hiccup_fn!(fn card(buf, title: &str) {
+            div.card[h2[(title)]]
+        });
+        hiccup_fn!(pub(crate) fn footer(buf) { footer["bye"] });
+        hiccup_fn!(fn page(w, titles: &[&str],) {
+            main[for (title in titles) [(Partial(|w| card(w, title)))] (Partial(|w| footer(w)))]
+        });
+        let mut out = String::new();
+
+        let _ = page(&mut out, &["a & b", "c"]);
+
+        assert_eq!(out, "<main><div class=\"card\"><h2>a &amp; b</h2></div><div class=\"card\"><h2>c</h2></div>\
+        <footer>bye</footer></main>");
+
+        let mut out = String::new();
+        let w: &mut dyn std::fmt::Write = &mut out;
+
+        let _ = card(w, "d");
+
+        assert_eq!(out, "<div class=\"card\"><h2>d</h2></div>");
+    }
+
+    #[test]
+    fn forced_self_close() {
+        let mut out = String::new();
+
+        let _ = hiccup!(&mut out, div[my-el{}/ my-icon{name=>"x"}/ span/ div.a{id=>"b"}/ br/ p["x"]]);
+        assert_eq!(out, "<div><my-el/><my-icon name=\"x\"/><span/><div id=\"b\" class=\"a\"/><br/><p>x</p></div>");
+
+        out.clear();
+        let _ = hiccup_strict!(&mut out, div[span/ x-a/]);
+        assert_eq!(out, "<div><span/><x-a/></div>");
+    }
+
+    #[test]
+    fn strict_known_tags() {
+        let mut out = String::new();
+        let items = ["a", "b"];
+
+        let _ = hiccup_strict!(&mut out,
+            doctype html[
+                body#main.page[
+                    if (true) [ul[for (i in &items) [li[(i)]]]] else [p["none"]]
+                    x-card{class=>"big"}[svg[path{d=>"M0 0"}]]
+                ]
+            ]);
+
+        assert_eq!(out, hiccup_string!(
+            doctype html[
+                body#main.page[
+                    if (true) [ul[for (i in &items) [li[(i)]]]] else [p["none"]]
+                    x-card{class=>"big"}[svg[path{d=>"M0 0"}]]
+                ]
+            ]));
     }
 }
@@ -0,0 +1,36 @@
+use axum::http::header::{self, HeaderValue};
+use axum::response::{IntoResponse, Response};
+
+/// A rendered page that an axum handler can return, sent with `Content-Type: text/html; charset=utf-8`.
+///
+/// ```rust
+/// use hiccup::{hiccup_string, HiccupPage};
+///
+/// async fn index() -> HiccupPage {
+///     HiccupPage(hiccup_string!(html[body[h1["Hiccup"]]]))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HiccupPage(pub String);
+
+impl IntoResponse for HiccupPage {
+    fn into_response(self) -> Response {
+        ([(header::CONTENT_TYPE, HeaderValue::from_static("text/html; charset=utf-8"))], self.0).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::header;
+    use axum::response::IntoResponse;
+
+    use super::HiccupPage;
+
+    #[test]
+    fn response_is_html() {
+        let response = HiccupPage(hiccup_string!(p["hi"])).into_response();
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/html; charset=utf-8");
+    }
+}
@@ -0,0 +1,17 @@
+//! The macro and the types most templates need, for a single `use hiccup::prelude::*;`.
+//!
+//! ```rust
+//! use hiccup::prelude::*;
+//!
+//! let mut html = String::new();
+//!
+//! let _ = hiccup!(&mut html, p[(PreEscaped("<b>hi</b>"))]);
+//!
+//! assert_eq!(html, "<p><b>hi</b></p>");
+//! ```
+
+pub use crate::escape::{escape_attr, escape_html};
+pub use crate::hiccup;
+#[cfg(feature = "std")]
+pub use crate::hiccup_io;
+pub use crate::{Element, PreEscaped, Render};
@@ -0,0 +1,757 @@
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Write};
+use core::mem;
+
+use crate::escape::{attr_escape, attr_single_escape, escape_html_ascii_to, escape_to, html_escape};
+use crate::is_void_element;
+
+const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "br", "button", "cite", "code", "data", "dfn", "em", "i", "img", "input",
+    "kbd", "label", "mark", "q", "s", "samp", "select", "small", "span", "strong", "sub", "sup", "textarea",
+    "time", "u", "var", "wbr",
+];
+
+// Elements whose text is rendered as is by the browsers, so minifying keeps its whitespace.
+const WHITESPACE_ELEMENTS: &[&str] = &["pre", "textarea"];
+
+// Elements whose text is code, written unescaped, and kept as is when minifying.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+// The attributes holding a URL that `safe_urls` checks, and the schemes that run script when followed.
+const URL_ATTRIBUTES: &[&str] = &["href", "src", "xlink:href", "action", "formaction"];
+const DANGEROUS_SCHEMES: &[&str] = &["javascript:", "vbscript:"];
+
+struct Open {
+    // Whether it has block children and its closing tag goes to its own line.
+    block_children: bool,
+    preserve_whitespace: bool,
+    raw_text: bool,
+    // `pre` and `textarea`, where the browsers drop a newline right after the start tag.
+    drops_first_newline: bool,
+}
+
+// What a renderer knows about the elements it's in, carried over between the renderers of one chunk each
+// of `Element::render_chunks`.
+#[derive(Default)]
+pub(crate) struct Context {
+    written: bool,
+    after_start: bool,
+    after_space: bool,
+    open: Vec<Open>,
+}
+
+/// Writes the tags, attributes and text emitted by the macros into `out`.
+/// Writing to the renderer directly with `write!` writes raw, unescaped, markup.
+#[doc(hidden)]
+pub struct Renderer<'a, W: Write + ?Sized> {
+    out: &'a mut W,
+    pretty: bool,
+    // The indentation unit and the line break of the pretty mode.
+    indent: &'a str,
+    line_break: &'a str,
+    minify: bool,
+    xml: bool,
+    // Void elements are written without the trailing slash, `<br>`.
+    html5: bool,
+    // The `_` in attribute keys are written as `-`, `http-equiv`.
+    kebab: bool,
+    // Tag names are written in lowercase, `<div>` for `Div`.
+    lower: bool,
+    // Non-ASCII characters in text are written as numeric references, `&#233;`.
+    ascii: bool,
+    // Attribute values are single quoted, `class='card'`, and their `'` escaped instead of `"`.
+    squote: bool,
+    // The URL values, like `href` and `src`, with a script scheme, like `javascript:`, are written as `#`.
+    safe_urls: bool,
+    // The elements self-closed instead of the HTML void elements.
+    void: Option<&'a [&'a str]>,
+    written: bool,
+    // In XML mode the `>` of a start tag waits for the first child, so an element without children is self-closed.
+    start_pending: bool,
+    // Nothing was written since the last start tag.
+    after_start: bool,
+    // The last byte written is a space, so minified text starting with whitespace doesn't write another one.
+    after_space: bool,
+    // The id and classes of the `div#main.card` shorthand, the classes wait to be merged with a `class` attribute.
+    id: Option<&'static str>,
+    class: Option<&'static str>,
+    opening: Option<Open>,
+    open: Vec<Open>,
+}
+
+impl<'a, W: Write + ?Sized> Renderer<'a, W> {
+    pub fn new(out: &'a mut W) -> Self {
+        Renderer {
+            out,
+            pretty: false,
+            indent: "  ",
+            line_break: "\n",
+            minify: false,
+            xml: false,
+            html5: false,
+            kebab: false,
+            lower: false,
+            ascii: false,
+            squote: false,
+            safe_urls: false,
+            void: None,
+            written: false,
+            start_pending: false,
+            after_start: false,
+            after_space: false,
+            id: None,
+            class: None,
+            opening: None,
+            open: Vec::new(),
+        }
+    }
+
+    // A renderer inside the elements of `context`, left by `suspend`.
+    pub(crate) fn resume(out: &'a mut W, context: Context) -> Self {
+        Renderer {
+            written: context.written,
+            after_start: context.after_start,
+            after_space: context.after_space,
+            open: context.open,
+            ..Renderer::new(out)
+        }
+    }
+
+    pub(crate) fn suspend(self) -> Context {
+        Context { written: self.written, after_start: self.after_start, after_space: self.after_space, open: self.open }
+    }
+
+    /// A renderer that puts every block element in its own line, indented by two spaces per level.
+    pub fn pretty(out: &'a mut W) -> Self {
+        Renderer { pretty: true, ..Renderer::new(out) }
+    }
+
+    /// A pretty renderer with the indentation and the line break of `config`.
+    pub fn pretty_with(out: &'a mut W, config: &'a PrettyConfig) -> Self {
+        Renderer { pretty: true, indent: &config.indent, line_break: &config.newline, ..Renderer::new(out) }
+    }
+
+    /// A renderer that collapses the whitespace in text into a single space, except inside `pre` and `textarea`.
+    pub fn minified(out: &'a mut W) -> Self {
+        Renderer { minify: true, ..Renderer::new(out) }
+    }
+
+    /// A renderer that writes void elements without the trailing slash, like `<br>`.
+    pub fn html5(out: &'a mut W) -> Self {
+        Renderer { html5: true, ..Renderer::new(out) }
+    }
+
+    /// A renderer that writes the `_` in attribute keys as `-`, like `http-equiv` for `http_equiv`.
+    pub fn kebab(out: &'a mut W) -> Self {
+        Renderer { kebab: true, ..Renderer::new(out) }
+    }
+
+    /// A renderer that writes the tag names in lowercase, like `<div>` for `Div`.
+    pub fn lower(out: &'a mut W) -> Self {
+        Renderer { lower: true, ..Renderer::new(out) }
+    }
+
+    /// A renderer that writes the non-ASCII characters of text as numeric character references, like `&#233;`.
+    pub fn ascii(out: &'a mut W) -> Self {
+        Renderer { ascii: true, ..Renderer::new(out) }
+    }
+
+    /// A renderer that writes the attribute values in single quotes, like `class='card'`.
+    pub fn squote(out: &'a mut W) -> Self {
+        Renderer { squote: true, ..Renderer::new(out) }
+    }
+
+    /// A renderer that writes the URL values, like `href` and `src`, with a script scheme, like `javascript:alert(1)`, as `#`.
+    pub fn safe_urls(out: &'a mut W) -> Self {
+        Renderer { safe_urls: true, ..Renderer::new(out) }
+    }
+
+    /// A renderer that self-closes the elements of `void` instead of the HTML void elements, for other SGML formats.
+    pub fn with_void(out: &'a mut W, void: &'a [&'a str]) -> Self {
+        Renderer { void: Some(void), ..Renderer::new(out) }
+    }
+
+    /// A renderer for XML, where every element without children is self-closed.
+    pub fn xml(out: &'a mut W) -> Self {
+        Renderer { xml: true, ..Renderer::new(out) }
+    }
+
+    /// Writes `<tag`, followed by the attributes and then `start_children` or `end_empty`.
+    pub fn open_tag(&mut self, tag: &str) -> fmt::Result {
+        let tag = &*self.tag_name(tag);
+        if self.pretty && !INLINE_ELEMENTS.contains(&tag) && !self.preserves_whitespace() {
+            if let Some(parent) = self.open.last_mut() {
+                parent.block_children = true;
+            }
+            self.newline()?;
+        }
+        let raw_text = RAW_TEXT_ELEMENTS.contains(&tag);
+        let drops_first_newline = WHITESPACE_ELEMENTS.contains(&tag);
+        let preserve_whitespace = self.preserves_whitespace() || raw_text || drops_first_newline;
+        self.opening = Some(Open { block_children: false, preserve_whitespace, raw_text, drops_first_newline });
+        write!(self, "<{}", tag)?;
+        match self.id.take() {
+            Some(id) => write!(self, " id={q}{}{q}", id, q = self.quote()),
+            None => Ok(()),
+        }
+    }
+
+    /// The id of the `div#main` shorthand for the next tag.
+    pub fn id_shorthand(&mut self, id: &'static str) {
+        self.id = Some(id);
+    }
+
+    /// The classes of the `div.card.big` shorthand for the next tag, appended before the ones of its `class` attribute.
+    pub fn class_shorthand(&mut self, class: &'static str) {
+        let class = class.trim_end();
+        self.class = if class.is_empty() { None } else { Some(class) };
+    }
+
+    pub fn attr<V: Display + ?Sized>(&mut self, key: &str, value: &V) -> fmt::Result {
+        let q = self.quote();
+        match self.class.take() {
+            Some(class) if key == "class" => {
+                write!(self, " class={}{}", q, class)?;
+                self.value(Some(" "), value)?;
+                self.write_char(q)
+            }
+            class => {
+                self.class = class;
+                let key = self.key(key);
+                let url = URL_ATTRIBUTES.iter().any(|url| url.eq_ignore_ascii_case(&key));
+                if self.safe_urls && url && is_dangerous_url(&value.to_string()) {
+                    return write!(self, " {}={q}#{q}", key, q = q);
+                }
+                write!(self, " {}={}", key, q)?;
+                self.value(None, value)?;
+                self.write_char(q)
+            }
+        }
+    }
+
+    /// Writes every `(key, value)` pair, in the order of the iterator.
+    /// A key that isn't an attribute name, like one with a space or a `=`, is an error, so the data can't add attributes.
+    pub fn attrs<I, K, V>(&mut self, attrs: I) -> fmt::Result
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Display,
+        V: Display,
+    {
+        for (key, value) in attrs {
+            let key = key.to_string();
+            if !is_valid_name(&key) {
+                return Err(fmt::Error);
+            }
+            self.attr(&key, &value)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the values separated by spaces, skipping the empty ones, and nothing if all of them are empty.
+    pub fn list_attr<I>(&mut self, key: &str, values: I) -> fmt::Result
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        let mut joined = String::new();
+        for value in values {
+            let value = value.to_string();
+            if !value.is_empty() {
+                if !joined.is_empty() {
+                    joined.push(' ');
+                }
+                joined.push_str(&value);
+            }
+        }
+        if joined.is_empty() {
+            return Ok(());
+        }
+        self.attr(key, &joined)
+    }
+
+    /// Writes the `name:value` pairs separated by `;`, with the `_` in the names written as `-`, like `font-weight:bold`.
+    pub fn style_attr(&mut self, key: &str, styles: &[(&str, &dyn Display)]) -> fmt::Result {
+        let mut joined = String::new();
+        for (name, value) in styles {
+            if !joined.is_empty() {
+                joined.push(';');
+            }
+            write!(joined, "{}:{}", name.replace('_', "-"), value)?;
+        }
+        self.attr(key, &joined)
+    }
+
+    pub fn bool_attr(&mut self, key: &str) -> fmt::Result {
+        // A bare `class` next to the `.card` shorthand is written once, with the shorthand classes.
+        if key == "class" && self.class.is_some() {
+            return self.attr(key, "");
+        }
+        let key = self.key(key);
+        write!(self, " {}", key)
+    }
+
+    pub fn start_children(&mut self) -> fmt::Result {
+        self.end_attrs()?;
+        if let Some(open) = self.opening.take() {
+            self.open.push(open);
+        }
+        if self.xml {
+            self.start_pending = true;
+        } else {
+            self.write_str(">")?;
+        }
+        self.after_start = true;
+        Ok(())
+    }
+
+    pub fn close_tag(&mut self, tag: &str) -> fmt::Result {
+        let tag = &*self.tag_name(tag);
+        let block_children = self.open.pop().is_some_and(|open| open.block_children);
+        if self.start_pending {
+            self.start_pending = false;
+            return self.out.write_str("/>");
+        }
+        if block_children && self.pretty {
+            self.newline()?;
+        }
+        write!(self, "</{}>", tag)
+    }
+
+    /// Ends a tag without children, void elements, or every element in XML mode, are self-closed.
+    pub fn end_empty(&mut self, tag: &str) -> fmt::Result {
+        let tag = &*self.tag_name(tag);
+        self.end_attrs()?;
+        self.opening = None;
+        if self.html5 && self.is_void(tag) {
+            self.write_str(">")
+        } else if self.xml || self.is_void(tag) {
+            self.write_str("/>")
+        } else {
+            write!(self, "></{}>", tag)
+        }
+    }
+
+    /// Ends a tag with empty children, `tag[]`, which are written, `<tag></tag>`, unless it's in the void set of `with_void`.
+    pub fn end_empty_children(&mut self, tag: &str) -> fmt::Result {
+        let custom_void = self.void.is_some() && self.is_void(&self.tag_name(tag));
+        if custom_void {
+            return self.end_empty(tag);
+        }
+        self.start_children()?;
+        self.close_tag(tag)
+    }
+
+    /// Ends a tag without children with `/>`, void or not.
+    pub fn self_close(&mut self) -> fmt::Result {
+        self.end_attrs()?;
+        self.opening = None;
+        self.write_str("/>")
+    }
+
+    /// Writes escaped text, or as is, except for end tags, inside `script` and `style`.
+    pub fn text<T: Display + ?Sized>(&mut self, value: &T) -> fmt::Result {
+        if self.open.last().is_some_and(|open| open.raw_text) {
+            return self.write_str(&split_end_tags(&value.to_string()));
+        }
+        let escape = if self.ascii { Escape::Ascii } else { Escape::Html };
+        let collapse = if self.minify && !self.preserves_whitespace() { Some(self.after_space) } else { None };
+        let first_newline = self.after_start && self.open.last().is_some_and(|open| open.drops_first_newline);
+        write!(Escaper { out: self, escape, prefix: None, collapse, first_newline }, "{}", value)
+    }
+
+    // Writes an attribute value escaped, after `prefix` when it isn't empty.
+    fn value<V: Display + ?Sized>(&mut self, prefix: Option<&'static str>, value: &V) -> fmt::Result {
+        let escape = if self.squote { Escape::AttrSingle } else { Escape::Attr };
+        write!(Escaper { out: self, escape, prefix, collapse: None, first_newline: false }, "{}", value)
+    }
+
+    fn tag_name<'t>(&self, tag: &'t str) -> Cow<'t, str> {
+        if self.lower {
+            Cow::Owned(tag.to_lowercase())
+        } else {
+            Cow::Borrowed(tag)
+        }
+    }
+
+    fn key<'k>(&self, key: &'k str) -> Cow<'k, str> {
+        if self.kebab {
+            Cow::Owned(key.replace('_', "-"))
+        } else {
+            Cow::Borrowed(key)
+        }
+    }
+
+    fn is_void(&self, tag: &str) -> bool {
+        match self.void {
+            Some(void) => void.contains(&tag),
+            None => is_void_element(tag),
+        }
+    }
+
+    fn quote(&self) -> char {
+        if self.squote {
+            '\''
+        } else {
+            '"'
+        }
+    }
+
+    fn preserves_whitespace(&self) -> bool {
+        self.open.last().is_some_and(|open| open.preserve_whitespace)
+    }
+
+    fn end_attrs(&mut self) -> fmt::Result {
+        match self.class.take() {
+            Some(class) => write!(self, " class={q}{}{q}", class, q = self.quote()),
+            None => Ok(()),
+        }
+    }
+
+    fn newline(&mut self) -> fmt::Result {
+        if !self.written {
+            return Ok(());
+        }
+        self.write_str(self.line_break)?;
+        for _ in 0..self.open.len() {
+            self.write_str(self.indent)?;
+        }
+        Ok(())
+    }
+}
+
+// Browsers ignore the leading spaces and control characters of a URL and the tabs and newlines inside of it,
+// so `" java\tscript:"` is as dangerous as `javascript:`.
+fn is_dangerous_url(url: &str) -> bool {
+    let url: String = url
+        .trim_start_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .take(DANGEROUS_SCHEMES.iter().map(|scheme| scheme.len()).max().unwrap_or(0))
+        .collect();
+    DANGEROUS_SCHEMES.iter().any(|scheme| url.get(..scheme.len()).is_some_and(|s| s.eq_ignore_ascii_case(scheme)))
+}
+
+// Whether a name known only at runtime can be written as is as a tag name or an attribute key,
+// it's not empty and has no whitespace, control characters, quotes, `>`, `/` or `=`, which would end it early.
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.chars().any(|c| c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '>' | '/' | '='))
+}
+
+enum Escape {
+    Html,
+    Ascii,
+    Attr,
+    AttrSingle,
+}
+
+// What the text and attribute values are formatted into, so they are escaped run by run as they are written,
+// without being copied into a `String` first.
+struct Escaper<'r, 'a, W: Write + ?Sized> {
+    out: &'r mut Renderer<'a, W>,
+    escape: Escape,
+    // Written before the first character, the space between the shorthand classes and the `class` value.
+    prefix: Option<&'static str>,
+    // When minifying, whether the last character was whitespace, so the whitespace that follows is dropped.
+    collapse: Option<bool>,
+    // The browsers drop a newline right after the start tag of a `pre`, so the one starting the text is doubled.
+    first_newline: bool,
+}
+
+impl<W: Write + ?Sized> Escaper<'_, '_, W> {
+    fn run(&mut self, s: &str) -> fmt::Result {
+        match self.escape {
+            Escape::Html => escape_to(s, self.out, html_escape),
+            Escape::Ascii => escape_html_ascii_to(s, self.out),
+            Escape::Attr => escape_to(s, self.out, attr_escape),
+            Escape::AttrSingle => escape_to(s, self.out, attr_single_escape),
+        }
+    }
+}
+
+impl<W: Write + ?Sized> Write for Escaper<'_, '_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+        if let Some(prefix) = self.prefix.take() {
+            self.out.write_str(prefix)?;
+        }
+        if mem::take(&mut self.first_newline) && s.starts_with('\n') {
+            self.out.write_str("\n")?;
+        }
+        let Some(mut in_whitespace) = self.collapse else {
+            return self.run(s);
+        };
+        let mut run = 0;
+        for (i, c) in s.char_indices() {
+            if c.is_whitespace() {
+                self.run(&s[run..i])?;
+                if !in_whitespace {
+                    self.out.write_str(" ")?;
+                }
+                run = i + c.len_utf8();
+            }
+            in_whitespace = c.is_whitespace();
+        }
+        self.collapse = Some(in_whitespace);
+        self.run(&s[run..])
+    }
+}
+
+// Writes `</script` and `</style` as `<\/script` and `<\/style`, which mean the same in JavaScript strings and CSS,
+// so the text of a `script` or `style` can't close it early.
+fn split_end_tags(s: &str) -> Cow<'_, str> {
+    let is_end_tag = |i: usize| {
+        RAW_TEXT_ELEMENTS.iter().any(|tag| {
+            s.get(i + 2..i + 2 + tag.len()).is_some_and(|name| name.eq_ignore_ascii_case(tag))
+        })
+    };
+    let ends: Vec<usize> = s.match_indices("</").map(|(i, _)| i).filter(|&i| is_end_tag(i)).collect();
+    if ends.is_empty() {
+        return Cow::Borrowed(s);
+    }
+    let mut split = String::with_capacity(s.len() + ends.len());
+    let mut last = 0;
+    for i in ends {
+        split.push_str(&s[last..=i]);
+        split.push('\\');
+        last = i + 1;
+    }
+    split.push_str(&s[last..]);
+    Cow::Owned(split)
+}
+
+impl<W: Write + ?Sized> Write for Renderer<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.written = true;
+        self.after_start = false;
+        if !s.is_empty() {
+            self.after_space = s.ends_with(' ');
+        }
+        if self.start_pending {
+            self.start_pending = false;
+            self.out.write_str(">")?;
+        }
+        self.out.write_str(s)
+    }
+}
+
+/// How `Element::render_pretty` breaks the lines and indents the block elements.
+/// The default is the one of `hiccup_pretty!`, two spaces and `\n`.
+///
+/// ```rust
+/// use hiccup::{Element, PrettyConfig};
+///
+/// let mut html = String::new();
+/// let config = PrettyConfig { indent: "\t".to_string(), ..PrettyConfig::default() };
+///
+/// Element::new("ul").child(Element::new("li")).render_pretty(&mut html, &config).unwrap();
+///
+/// assert_eq!(html, "<ul>\n\t<li></li>\n</ul>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrettyConfig {
+    /// Written once per nesting level.
+    pub indent: String,
+    /// Written before every block element and before the end tag of their parents, like `\n` or `\r\n`.
+    pub newline: String,
+}
+
+impl Default for PrettyConfig {
+    fn default() -> Self {
+        PrettyConfig { indent: String::from("  "), newline: String::from("\n") }
+    }
+}
+
+/// A component that can be embedded as a child of the macros, like `div[(card)]`.
+/// Unlike `Display` values, which are escaped, `render` writes markup, usually with `hiccup!(w, ...)`.
+/// The component only gets a writer, so its `hiccup!` doesn't know the modes of the caller:
+/// inside `hiccup_safe_urls!`, `hiccup_pretty!`, `hiccup_squote!` or `hiccup_xml!` its markup is the one of `hiccup!`.
+///
+/// ```rust
+/// use hiccup::{hiccup, Render};
+///
+/// struct Card<'a> {
+///     title: &'a str,
+/// }
+///
+/// impl Render for Card<'_> {
+///     fn render(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+///         hiccup!(w, div{class=>"card"}[h2[(self.title)]])
+///     }
+/// }
+///
+/// let mut html = String::new();
+/// let card = Card { title: "Hiccup" };
+///
+/// let _ = hiccup!(&mut html, main[(card)]);
+///
+/// assert_eq!(html, "<main><div class=\"card\"><h2>Hiccup</h2></div></main>");
+/// ```
+pub trait Render {
+    fn render(&self, w: &mut dyn Write) -> fmt::Result;
+}
+
+impl<T: Render + ?Sized> Render for &T {
+    fn render(&self, w: &mut dyn Write) -> fmt::Result {
+        (**self).render(w)
+    }
+}
+
+/// Marks a trusted value, already valid HTML, so it's rendered without escaping, like `p[(PreEscaped(html))]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PreEscaped<T>(pub T);
+
+impl<T: Display> Display for PreEscaped<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<T: Display> Render for PreEscaped<T> {
+    fn render(&self, w: &mut dyn Write) -> fmt::Result {
+        write!(w, "{}", self.0)
+    }
+}
+
+/// An owned fragment of trusted HTML, like the output of another template engine, rendered as is, like `div[(Safe(fragment))]`.
+/// Unlike `PreEscaped` it isn't `Display`, so it can't end up escaped in an attribute by mistake.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Safe(pub String);
+
+impl From<String> for Safe {
+    fn from(html: String) -> Self {
+        Safe(html)
+    }
+}
+
+impl Render for Safe {
+    fn render(&self, w: &mut dyn Write) -> fmt::Result {
+        w.write_str(&self.0)
+    }
+}
+
+/// A function writing markup, like a partial of `hiccup_fn!`, embedded as a child, `main[(Partial(|w| card(w, "a")))]`.
+/// It writes straight into the writer of the macro around it, without a `String` of its own.
+pub struct Partial<F: Fn(&mut dyn Write) -> fmt::Result>(pub F);
+
+impl<F: Fn(&mut dyn Write) -> fmt::Result> Render for Partial<F> {
+    fn render(&self, w: &mut dyn Write) -> fmt::Result {
+        (self.0)(w)
+    }
+}
+
+/// The component of `head_defaults!`.
+#[doc(hidden)]
+pub struct HeadDefaults<T>(pub T);
+
+impl<T: Display> Render for HeadDefaults<T> {
+    fn render(&self, w: &mut dyn Write) -> fmt::Result {
+        crate::hiccup_html5!(w, head[
+            meta_charset
+            meta{name=>"viewport", content=>"width=device-width, initial-scale=1"}
+            title[(self.0)]
+        ])
+    }
+}
+
+// Expression children are rendered with `value.__hiccup_kind().render(value, w)`, where `value` is a reference.
+// Method resolution picks `RenderKind` for `Render` types before autoref'ing into `TextKind`,
+// so only the values that are just `Display` are escaped. Iterators of `Render` items, which are neither, get `IterKind`.
+
+#[doc(hidden)]
+pub struct Component;
+
+impl Component {
+    pub fn render<T: Render + ?Sized, W: Write + ?Sized>(self, value: &T, w: &mut Renderer<W>) -> fmt::Result {
+        value.render(w)
+    }
+}
+
+#[doc(hidden)]
+pub trait RenderKind {
+    fn __hiccup_kind(&self) -> Component {
+        Component
+    }
+}
+
+impl<T: Render + ?Sized> RenderKind for T {}
+#[doc(hidden)]
+pub struct Text;
+
+impl Text {
+    pub fn render<T: Display + ?Sized, W: Write + ?Sized>(self, value: &T, w: &mut Renderer<W>) -> fmt::Result {
+        w.text(value)
+    }
+}
+
+#[doc(hidden)]
+pub trait TextKind {
+    fn __hiccup_kind(&self) -> Text {
+        Text
+    }
+}
+
+impl<T: Display + ?Sized> TextKind for &T {}
+
+#[doc(hidden)]
+pub struct Components;
+
+impl Components {
+    // Only a reference to the iterator is given, so a clone of it is consumed.
+    pub fn render<I, W>(self, iter: &I, w: &mut Renderer<W>) -> fmt::Result
+    where
+        I: Iterator + Clone,
+        I::Item: Render,
+        W: Write + ?Sized,
+    {
+        for component in iter.clone() {
+            component.render(w)?;
+        }
+        Ok(())
+    }
+}
+
+#[doc(hidden)]
+pub trait IterKind {
+    fn __hiccup_kind(&self) -> Components {
+        Components
+    }
+}
+
+impl<I: Iterator + Clone> IterKind for &I where I::Item: Render {}
+
+#[doc(hidden)]
+pub struct Collection;
+
+impl Collection {
+    pub fn render<'c, C, W>(self, items: &'c C, w: &mut Renderer<W>) -> fmt::Result
+    where
+        C: ?Sized,
+        &'c C: IntoIterator,
+        <&'c C as IntoIterator>::Item: Render,
+        W: Write + ?Sized,
+    {
+        for component in items {
+            component.render(w)?;
+        }
+        Ok(())
+    }
+}
+
+// A `Vec`, a slice or an array of components, rendered through a reference, so it isn't consumed.
+#[doc(hidden)]
+pub trait CollectionKind {
+    fn __hiccup_kind(&self) -> Collection {
+        Collection
+    }
+}
+
+impl<'c, C: ?Sized> CollectionKind for &'c C
+where
+    &'c C: IntoIterator,
+    <&'c C as IntoIterator>::Item: Render,
+{
+}
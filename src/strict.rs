@@ -0,0 +1,114 @@
+// The tags known by `hiccup_strict!`, HTML5 elements and the usual inline SVG ones.
+const HTML_TAGS: &[&str] = &[
+    "a", "abbr", "address", "area", "article", "aside", "audio", "b", "base", "bdi", "bdo", "blockquote", "body",
+    "br", "button", "canvas", "caption", "cite", "code", "col", "colgroup", "data", "datalist", "dd", "del",
+    "details", "dfn", "dialog", "div", "dl", "dt", "em", "embed", "fieldset", "figcaption", "figure", "footer",
+    "form", "h1", "h2", "h3", "h4", "h5", "h6", "head", "header", "hgroup", "hr", "html", "i", "iframe", "img",
+    "input", "ins", "kbd", "label", "legend", "li", "link", "main", "map", "mark", "menu", "meta", "meter", "nav",
+    "noscript", "object", "ol", "optgroup", "option", "output", "p", "param", "picture", "pre", "progress", "q",
+    "rp", "rt", "ruby", "s", "samp", "script", "search", "section", "select", "slot", "small", "source", "span",
+    "strong", "style", "sub", "summary", "sup", "table", "tbody", "td", "template", "textarea", "tfoot", "th",
+    "thead", "time", "title", "tr", "track", "u", "ul", "var", "video", "wbr",
+    // SVG
+    "svg", "g", "defs", "symbol", "use", "path", "circle", "ellipse", "line", "polygon", "polyline", "rect", "text",
+    "tspan", "image", "clipPath", "mask", "pattern", "linearGradient", "radialGradient", "stop", "foreignObject",
+];
+
+// The named character references accepted by `entity(name)`, the usual ones of text and typography.
+const ENTITIES: &[&str] = &[
+    "nbsp", "amp", "lt", "gt", "quot", "apos", "copy", "reg", "trade", "mdash", "ndash", "hellip", "laquo", "raquo",
+    "lsquo", "rsquo", "sbquo", "ldquo", "rdquo", "bdquo", "bull", "middot", "deg", "plusmn", "times", "divide", "minus",
+    "micro", "para", "sect", "cent", "pound", "yen", "euro", "curren", "iexcl", "iquest", "shy", "ensp", "emsp",
+    "thinsp", "zwj", "zwnj", "larr", "rarr", "uarr", "darr", "harr", "lArr", "rArr", "hearts", "spades", "clubs",
+    "diams", "check", "cross", "star", "frac12", "frac14", "frac34", "sup1", "sup2", "sup3", "permil", "prime", "Prime",
+    "dagger", "Dagger", "infin", "ne", "le", "ge", "asymp", "sum", "radic",
+];
+
+// The elements that close a `p`, so they can't be inside one.
+const BLOCK_TAGS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "details", "dialog", "div", "dl", "fieldset", "figcaption", "figure",
+    "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6", "header", "hgroup", "hr", "main", "menu", "nav", "ol", "p",
+    "pre", "search", "section", "table", "ul",
+];
+
+// The roles accepted by `role=>"..."` in `hiccup_strict!`, the concrete WAI-ARIA 1.2 and graphics roles.
+const ROLES: &[&str] = &[
+    "alert", "alertdialog", "application", "article", "banner", "blockquote", "button", "caption", "cell", "checkbox",
+    "code", "columnheader", "combobox", "complementary", "contentinfo", "definition", "deletion", "dialog", "directory",
+    "document", "emphasis", "feed", "figure", "form", "generic", "graphics-document", "graphics-object",
+    "graphics-symbol", "grid", "gridcell", "group", "heading", "img", "insertion", "link", "list", "listbox",
+    "listitem", "log", "main", "marquee", "math", "menu", "menubar", "menuitem", "menuitemcheckbox", "menuitemradio",
+    "meter", "navigation", "none", "note", "option", "paragraph", "presentation", "progressbar", "radio", "radiogroup",
+    "region", "row", "rowgroup", "rowheader", "scrollbar", "search", "searchbox", "separator", "slider", "spinbutton",
+    "status", "strong", "subscript", "superscript", "switch", "tab", "table", "tablist", "tabpanel", "term", "textbox",
+    "time", "timer", "toolbar", "tooltip", "tree", "treegrid", "treeitem",
+];
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn contains(list: &[&str], s: &str) -> bool {
+    contains_bytes(list, s.as_bytes())
+}
+
+const fn contains_bytes(list: &[&str], s: &[u8]) -> bool {
+    let mut i = 0;
+    while i < list.len() {
+        if bytes_eq(list[i].as_bytes(), s) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// Whether `tag` is known by `hiccup_strict!`, evaluated at compile time.
+#[doc(hidden)]
+pub const fn is_known_tag(tag: &str) -> bool {
+    contains(HTML_TAGS, tag)
+}
+
+/// Whether `tag` is a block element, which can't be inside a `p`, evaluated at compile time.
+#[doc(hidden)]
+pub const fn is_block_tag(tag: &str) -> bool {
+    contains(BLOCK_TAGS, tag)
+}
+
+/// Whether `name` is a character reference accepted by `entity(name)`, evaluated at compile time.
+#[doc(hidden)]
+pub const fn is_known_entity(name: &str) -> bool {
+    contains(ENTITIES, name)
+}
+
+/// Whether every role of the space separated `role`, like `"switch button"`, is an ARIA role, evaluated at compile time.
+#[doc(hidden)]
+pub const fn is_known_role(role: &str) -> bool {
+    let mut rest = role.as_bytes();
+    let mut any = false;
+    while !rest.is_empty() {
+        let mut end = 0;
+        while end < rest.len() && rest[end] != b' ' {
+            end += 1;
+        }
+        let (token, after) = rest.split_at(end);
+        if !token.is_empty() {
+            if !contains_bytes(ROLES, token) {
+                return false;
+            }
+            any = true;
+        }
+        rest = if after.is_empty() { after } else { after.split_at(1).1 };
+    }
+    any
+}
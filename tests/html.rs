@@ -1,6 +1,6 @@
 extern crate hiccup;
 
-use hiccup::hiccup;
+use hiccup::{hiccup, hiccup_kebab};
 
 #[test]
 fn html_templating() {
@@ -18,5 +18,42 @@ fn html_templating() {
     assert_eq!(out,"<html><head><meta name=\"author\" content=\"Julia Naomi\"/>\
     <title>Hiccup guide</title></head><body class=\"amazing hiccup guide\">\
     <h1 font=\"bold\" color=\"red\">Hiccup is the best!</h1>\
-    <p>please lookup clojure\'s hiccup for better ideas on this macro</p></body></html>");
-}
\ No newline at end of file
+    <p>please lookup clojure&#39;s hiccup for better ideas on this macro</p></body></html>");
+}
+
+#[test]
+fn responsive_picture() {
+    let mut out = String::new();
+
+    let _ = hiccup!(&mut out,
+        picture[
+            source{srcset=>"a.webp", type=>"image/webp"}
+            source{srcset=>"a-wide.jpg 2x", media=>"(min-width: 800px)"}
+            img{src=>"a.jpg", alt=>"x"}
+        ]);
+
+    assert_eq!(out, "<picture><source srcset=\"a.webp\" type=\"image/webp\"/>\
+    <source srcset=\"a-wide.jpg 2x\" media=\"(min-width: 800px)\"/><img src=\"a.jpg\" alt=\"x\"/></picture>");
+}
+
+#[test]
+fn htmx_attributes() {
+    let mut out = String::new();
+    let mut kebab = String::new();
+    let id = 7;
+
+    let _ = hiccup!(&mut out,
+        div[
+            button{"hx-get"=>"/data", "hx-target"=>"#out", "hx-swap"=>"innerHTML"}["Load"]
+            form{"hx-post"=>(format!("/items/{}", id)), "hx-swap"=>"outerHTML", "hx-confirm"=>"Sure?"}[
+                input{name=>"q", "hx-trigger"=>"keyup changed delay:500ms"}
+            ]
+            div#out[]
+        ]);
+    let _ = hiccup_kebab!(&mut kebab, button{hx_get=>"/data", hx_target=>"#out", hx_swap=>"innerHTML"}["Load"]);
+
+    assert_eq!(out, "<div><button hx-get=\"/data\" hx-target=\"#out\" hx-swap=\"innerHTML\">Load</button>\
+    <form hx-post=\"/items/7\" hx-swap=\"outerHTML\" hx-confirm=\"Sure?\">\
+    <input name=\"q\" hx-trigger=\"keyup changed delay:500ms\"/></form><div id=\"out\"></div></div>");
+    assert_eq!(kebab, "<button hx-get=\"/data\" hx-target=\"#out\" hx-swap=\"innerHTML\">Load</button>");
+}
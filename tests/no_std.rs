@@ -0,0 +1,12 @@
+use std::process::Command;
+
+#[test]
+fn builds_without_std() {
+    let status = Command::new(env!("CARGO"))
+        .args(["check", "--quiet", "--manifest-path", "tests/no_std/Cargo.toml"])
+        .env("CARGO_TARGET_DIR", "target/no_std")
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+}
@@ -0,0 +1,20 @@
+//! Built by `tests/no_std.rs`, to check that the macros and the escaping only need `core` and `alloc`.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+pub fn card(title: &str, body: &str) -> String {
+    let mut out = String::new();
+    let _ = hiccup::hiccup!(&mut out, div.card{title=>(title)}[h2[(title)] p[text!("{} & more", body)] br]);
+    out
+}
+
+pub fn escaped(text: &str) -> String {
+    hiccup::escape_html(text)
+}
+
+pub fn page() -> String {
+    hiccup::hiccup_string!(doctype html[body[comment["x"] raw["<hr>"]]])
+}
@@ -0,0 +1,29 @@
+extern crate hiccup;
+
+use hiccup::prelude::*;
+
+struct Badge(&'static str);
+
+impl Render for Badge {
+    fn render(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        hiccup!(w, span.badge[(self.0)])
+    }
+}
+
+#[test]
+fn prelude_is_enough() {
+    let mut out = String::new();
+    let card = Element::new("div").attr("class", "card").child(Element::text("hi"));
+
+    let _ = hiccup!(&mut out, main[(Badge("new")) (card) (PreEscaped("<hr/>"))]);
+
+    assert_eq!(out, "<main><span class=\"badge\">new</span><div class=\"card\">hi</div><hr/></main>");
+    assert_eq!(escape_html("<a & b>"), "&lt;a &amp; b&gt;");
+    assert_eq!(escape_attr("\"x\""), "&quot;x&quot;");
+
+    let mut bytes = Vec::new();
+
+    hiccup_io!(&mut bytes, p["io"]).unwrap();
+
+    assert_eq!(bytes, b"<p>io</p>");
+}
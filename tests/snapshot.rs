@@ -0,0 +1,58 @@
+extern crate hiccup;
+
+use hiccup::hiccup;
+
+// The whole output is pinned, any change to it has to be deliberate, see "Output stability" in the README.
+const EXPECTED: &str = "<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\"/>\
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\"/><title>Tom &amp; Jerry</title>\
+<link rel=\"stylesheet\" href=\"/style.css\"/><style>p > b { color: red }</style></head>\
+<body id=\"top\" class=\"page dark\"><!--nav--><nav><ul>\
+<li><a href=\"/\" class=\"active\">Home</a></li><li><a href=\"/about\">About</a></li></ul></nav>\
+<main><h1>Episodes</h1><table><tr><td>1</td><td>Puss Gets the Boot</td></tr>\
+<tr><td>2</td><td>The Midnight Snack</td></tr></table>\
+<p title=\"&quot;quoted&quot; &amp; more\">It&#39;s <b>&lt;great&gt;</b>&nbsp;&mdash; <i>really</i></p>\
+<form action=\"/search\" method=\"get\"><input type=\"search\" name=\"q\" required/>\
+<button type=\"submit\" disabled>Search</button></form>\
+<pre>  keep\n    this</pre><svg viewBox=\"0 0 10 10\"><path d=\"M0 0 L10 10\"/></svg></main>\
+<footer><p style=\"color:gray;font-size:small\">&copy; 2024</p></footer>\
+<script>if (a < b) {}</script></body></html>";
+
+#[test]
+fn page_output_is_stable() {
+    let mut out = String::new();
+    let links = [("/", "Home"), ("/about", "About")];
+    let episodes = ["Puss Gets the Boot", "The Midnight Snack"];
+    let busy = true;
+
+    let _ = hiccup!(&mut out,
+        doctype html{lang=>"en"}[
+            head[
+                meta_charset
+                meta{name=>"viewport", content=>"width=device-width, initial-scale=1"}
+                title["Tom & Jerry"]
+                link{rel=>"stylesheet", href=>"/style.css"}
+                style["p > b { color: red }"]
+            ]
+            body#top.page{class=>["dark" if busy]}[
+                comment["nav"]
+                nav[ul[for ((href, name) in links.iter()) [
+                    li[a{href=>(href), class=>?(if *href == "/" { Some("active") } else { None })}[(name)]]
+                ]]]
+                main[
+                    h1["Episodes"]
+                    table[for ((i, name) in episodes.iter().enumerate()) [tr[td[(i + 1)] td[(name)]]]]
+                    p{title=>"\"quoted\" & more"}["It's " b["<great>"] entity(nbsp) entity(mdash) " " i["really"]]
+                    form{action=>"/search", method=>"get"}[
+                        input{type=>"search", name=>"q", required}
+                        button{type=>"submit", disabled if busy}["Search"]
+                    ]
+                    pre["  keep\n    this"]
+                    svg{viewBox=>"0 0 10 10"}[path{d=>"M0 0 L10 10"}]
+                ]
+                footer[p{style=>{color=>"gray", font_size=>"small"}}[entity(copy) " 2024"]]
+                script["if (a < b) {}"]
+            ]
+        ]);
+
+    assert_eq!(out, EXPECTED);
+}
@@ -0,0 +1,8 @@
+use hiccup::{hiccup, PreEscaped};
+
+fn main() {
+    let mut out = String::new();
+    let bold = PreEscaped("<b>x</b>");
+
+    let _ = hiccup!(&mut out, p[(&bold)]);
+}
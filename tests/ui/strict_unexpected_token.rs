@@ -0,0 +1,7 @@
+use hiccup::hiccup_strict;
+
+fn main() {
+    let mut out = String::new();
+
+    let _ = hiccup_strict!(&mut out, div[=> "x"]);
+}
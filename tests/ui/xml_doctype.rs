@@ -0,0 +1,7 @@
+use hiccup::hiccup_xml;
+
+fn main() {
+    let mut out = String::new();
+
+    let _ = hiccup_xml!(&mut out, doctype rss[]);
+}